@@ -0,0 +1,379 @@
+//! `transrich`: applies a pipeline config (see `venum_tds_transrich::config`) to a CSV file.
+//!
+//! Every record becomes a `DataCellRow` of `String` items, named after the header, unless a type
+//! hint (`--type qty=u16`) says otherwise. Empty fields have no data. The output columns are the
+//! schema of the pipeline applied to the header, which is checked before any record is read.
+
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    path::PathBuf,
+    process::ExitCode,
+    str::FromStr,
+};
+
+use clap::{Parser, ValueEnum};
+use venum::venum::Value;
+use venum_tds::{cell::DataCell, row::DataCellRow, traits::VDataContainerItem};
+use venum_tds_transrich::{
+    config::{load_pipeline, TypeDescr},
+    pipeline::Pipeline,
+    schema::{format_schema, schema_from_names},
+    value_conversion::{convert_with, value_to_string, ConversionOptions},
+};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "transrich",
+    version,
+    about = "Applies a transformation pipeline to a CSV file"
+)]
+struct Args {
+    /// The pipeline config (.json, .yaml/.yml or .toml)
+    #[arg(short, long)]
+    config: PathBuf,
+    /// The CSV to read. Reads from stdin, if missing or `-`
+    input: Option<PathBuf>,
+    /// Where to write the CSV to. Writes to stdout, if missing or `-`
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// The field delimiter of the input (and the output, unless `--output-delimiter` is given)
+    #[arg(short, long, default_value = ",", value_parser = ascii_char)]
+    delimiter: u8,
+    #[arg(long, value_parser = ascii_char)]
+    output_delimiter: Option<u8>,
+    /// The quote char of the input and the output
+    #[arg(long, default_value = "\"", value_parser = ascii_char)]
+    quote: u8,
+    /// When to quote fields of the output
+    #[arg(long, value_enum, default_value = "necessary")]
+    quoting: Quoting,
+    /// The input has no header. The columns are named `col_0`, `col_1`, ...
+    #[arg(long)]
+    no_header: bool,
+    /// Don't write a header
+    #[arg(long)]
+    no_output_header: bool,
+    /// The type of an input column, e.g. `qty=u16` or `due=date:%d.%m.%Y`. Can be repeated
+    #[arg(short = 't', long = "type", value_name = "COLUMN=TYPE")]
+    types: Vec<TypeHint>,
+    /// Write the records of failed rows to this CSV, with the error as an additional last column.
+    /// Otherwise the errors are reported on stderr
+    #[arg(long, value_name = "PATH")]
+    errors: Option<PathBuf>,
+    /// Only process the first N records, e.g. to preview the output
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+    /// Only check the pipeline against the header and print the schema of the output
+    #[arg(long)]
+    check: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Quoting {
+    Necessary,
+    Always,
+    NonNumeric,
+    Never,
+}
+
+impl From<Quoting> for csv::QuoteStyle {
+    fn from(quoting: Quoting) -> Self {
+        match quoting {
+            Quoting::Necessary => csv::QuoteStyle::Necessary,
+            Quoting::Always => csv::QuoteStyle::Always,
+            Quoting::NonNumeric => csv::QuoteStyle::NonNumeric,
+            Quoting::Never => csv::QuoteStyle::Never,
+        }
+    }
+}
+
+fn ascii_char(s: &str) -> Result<u8, String> {
+    match s.as_bytes() {
+        [b] if b.is_ascii() => Ok(*b),
+        _ => Err(format!("'{}' is not a single ASCII char", s)),
+    }
+}
+
+/// A `--type` argument: the name of a column and its type.
+#[derive(Debug, Clone, PartialEq)]
+struct TypeHint {
+    column: String,
+    type_descr: TypeDescr,
+}
+
+impl FromStr for TypeHint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (column, type_descr) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected COLUMN=TYPE, got '{}'", s))?;
+        Ok(TypeHint {
+            column: String::from(column),
+            type_descr: type_descr.parse()?,
+        })
+    }
+}
+
+/// The typed schema of the input and the conversion options of each column (by idx).
+struct InputSchema {
+    schema: DataCellRow,
+    conversions: Vec<ConversionOptions>,
+}
+
+impl InputSchema {
+    fn new(names: &[String], types: &[TypeHint]) -> Result<Self, String> {
+        let mut schema = schema_from_names(names);
+        let mut conversions = vec![ConversionOptions::default(); names.len()];
+        for hint in types {
+            let cell = schema
+                .0
+                .iter_mut()
+                .find(|dc| dc.name == hint.column)
+                .ok_or_else(|| format!("--type {}: there is no such column", hint.column))?;
+            cell.type_info = hint.type_descr.type_info.clone();
+            conversions[cell.idx] = hint.type_descr.conversion(&ConversionOptions::default());
+        }
+        Ok(InputSchema {
+            schema,
+            conversions,
+        })
+    }
+
+    /// The row of a record, with every field converted into the type of its column.
+    fn row(&self, record: &csv::StringRecord) -> Result<DataCellRow, String> {
+        if record.len() != self.schema.0.len() {
+            return Err(format!(
+                "expected {} fields, got {}",
+                self.schema.0.len(),
+                record.len()
+            ));
+        }
+        let mut row = DataCellRow::new();
+        for ((cell, conversion), field) in self.schema.0.iter().zip(&self.conversions).zip(record) {
+            let data = match field {
+                "" => None,
+                _ => convert_with(
+                    &Value::from(String::from(field)),
+                    &cell.type_info,
+                    conversion,
+                )
+                .map_err(|e| format!("column {}: {}", cell.name, e))?,
+            };
+            row.0.push(DataCell::new(
+                cell.type_info.clone(),
+                cell.name.clone(),
+                cell.idx,
+                data,
+            ));
+        }
+        Ok(row)
+    }
+}
+
+/// The fields of `row` in the order of the output schema. Items the row doesn't have are empty.
+fn output_fields(row: &DataCellRow, output_schema: &[usize]) -> Vec<String> {
+    output_schema
+        .iter()
+        .map(|idx| {
+            row.0
+                .iter()
+                .find(|dc| dc.get_idx() == *idx)
+                .and_then(|dc| dc.data.as_ref())
+                .map(value_to_string)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn input(args: &Args) -> Result<Box<dyn Read>, String> {
+    match &args.input {
+        Some(path) if path.as_os_str() != "-" => Ok(Box::new(
+            File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?,
+        )),
+        _ => Ok(Box::new(io::stdin())),
+    }
+}
+
+fn output(path: Option<&PathBuf>) -> Result<Box<dyn Write>, String> {
+    match path {
+        Some(path) if path.as_os_str() != "-" => Ok(Box::new(
+            File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?,
+        )),
+        _ => Ok(Box::new(io::stdout())),
+    }
+}
+
+fn csv_writer(args: &Args, path: Option<&PathBuf>) -> Result<csv::Writer<Box<dyn Write>>, String> {
+    Ok(csv::WriterBuilder::new()
+        .delimiter(args.output_delimiter.unwrap_or(args.delimiter))
+        .quote(args.quote)
+        .quote_style(args.quoting.into())
+        .flexible(true)
+        .from_writer(output(path)?))
+}
+
+/// Applies the pipeline to all records. Returns the number of failed rows.
+fn run(args: &Args) -> Result<usize, String> {
+    let pipeline: Pipeline<DataCellRow> = load_pipeline(&args.config).map_err(|e| e.to_string())?;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(args.delimiter)
+        .quote(args.quote)
+        .has_headers(!args.no_header)
+        .flexible(true)
+        .from_reader(input(args)?);
+    // without a header, this is the first record, which is not consumed
+    let header = reader.headers().map_err(|e| e.to_string())?.clone();
+    let names: Vec<String> = match args.no_header {
+        true => (0..header.len())
+            .map(|idx| format!("col_{}", idx))
+            .collect(),
+        false => header.iter().map(String::from).collect(),
+    };
+    let input_schema = InputSchema::new(&names, &args.types)?;
+
+    let mut schema = input_schema.schema.clone();
+    let outcome = pipeline.check_schema(&mut schema);
+    for (step, e) in outcome.errors() {
+        eprintln!("step '{}' fails for every row: {}", step, e);
+    }
+    if outcome.is_failed() {
+        return Err(String::from("the pipeline doesn't fit the input"));
+    }
+    schema.0.sort_by_key(|dc| dc.get_idx());
+
+    if args.check {
+        write!(output(args.output.as_ref())?, "{}", format_schema(&schema))
+            .map_err(|e| e.to_string())?;
+        return Ok(0);
+    }
+    let mut writer = csv_writer(args, args.output.as_ref())?;
+    if !args.no_output_header {
+        writer
+            .write_record(schema.0.iter().map(|dc| dc.name.as_str()))
+            .map_err(|e| e.to_string())?;
+    }
+    let mut error_writer = match &args.errors {
+        Some(path) => {
+            let mut w = csv_writer(args, Some(path))?;
+            if !args.no_header {
+                w.write_record(names.iter().map(String::as_str).chain(["error"]))
+                    .map_err(|e| e.to_string())?;
+            }
+            Some(w)
+        }
+        None => None,
+    };
+
+    let output_schema: Vec<usize> = schema.0.iter().map(|dc| dc.get_idx()).collect();
+    let mut failed = 0;
+    for record in reader.records().take(args.limit.unwrap_or(usize::MAX)) {
+        let record = record.map_err(|e| e.to_string())?;
+        let res = input_schema.row(&record).and_then(|mut row| {
+            match pipeline.apply_row(&mut row).failure() {
+                Some((step, e)) => Err(format!("step '{}': {}", step, e)),
+                None => Ok(row),
+            }
+        });
+        match res {
+            Ok(row) => writer
+                .write_record(output_fields(&row, &output_schema))
+                .map_err(|e| e.to_string())?,
+            Err(e) => {
+                failed += 1;
+                match error_writer.as_mut() {
+                    Some(w) => w
+                        .write_record(record.iter().chain([e.as_str()]))
+                        .map_err(|e| e.to_string())?,
+                    None => {
+                        let line = record.position().map(|p| p.line()).unwrap_or_default();
+                        eprintln!("line {}: {}", line, e);
+                    }
+                }
+            }
+        }
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    if let Some(w) = error_writer.as_mut() {
+        w.flush().map_err(|e| e.to_string())?;
+    }
+    Ok(failed)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(failed) => {
+            eprintln!("{} row(s) failed", failed);
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("transrich: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[&str]) -> csv::StringRecord {
+        csv::StringRecord::from(fields.to_vec())
+    }
+
+    #[test]
+    fn test_type_hint() {
+        let hint: TypeHint = "due=date:%d.%m.%Y".parse().unwrap();
+        assert_eq!("due", hint.column);
+        assert_eq!(Value::naive_date_default(), hint.type_descr.type_info);
+        assert!("due".parse::<TypeHint>().is_err());
+        assert!("due=float".parse::<TypeHint>().is_err());
+    }
+
+    #[test]
+    fn test_typed_rows() {
+        let names = vec![String::from("name"), String::from("qty")];
+        let input_schema = InputSchema::new(&names, &["qty=u16".parse().unwrap()]).unwrap();
+        assert_eq!(
+            "  0 name string\n  1 qty  u16\n",
+            format_schema(&input_schema.schema)
+        );
+
+        let row = input_schema.row(&record(&["nut", "12"])).unwrap();
+        assert_eq!(Some(Value::UInt16(12)), row.0[1].data);
+        let row = input_schema.row(&record(&["nut", ""])).unwrap();
+        assert_eq!(None, row.0[1].data);
+        assert!(input_schema.row(&record(&["nut", "x"])).is_err());
+        assert_eq!(
+            Err(String::from("expected 2 fields, got 1")),
+            input_schema.row(&record(&["nut"]))
+        );
+
+        assert!(InputSchema::new(&names, &["price=f32".parse().unwrap()]).is_err());
+    }
+
+    #[test]
+    fn test_output_fields() {
+        let mut row = DataCellRow::new();
+        row.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("b"),
+            2,
+            Some(Value::from(String::from("x"))),
+        ));
+        row.0.push(DataCell::new(
+            Value::int32_default(),
+            String::from("a"),
+            0,
+            Some(Value::Int32(7)),
+        ));
+        assert_eq!(
+            vec![String::from("7"), String::new(), String::from("x")],
+            output_fields(&row, &[0, 1, 2])
+        );
+    }
+}
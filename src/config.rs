@@ -0,0 +1,1634 @@
+//! Declarative pipeline definitions, loaded from JSON, YAML or TOML.
+//!
+//! A config is a list of steps, each with an (optional) name, an error policy and exactly one op.
+//! Ops, splitters and mergers are written as `{ <kind>: { <fields> } }`, item selectors as idx,
+//! name or one of `{ range: [start, end] }`, `{ name_ignore_case: .. }`, `{ name_regex: .. }`,
+//! `{ type: .. }`, and target types as strings like `"f32"` or `"date:%Y-%m-%d"` (see
+//! `TypeDescr`). In YAML:
+//!
+//! ```yaml
+//! steps:
+//!   - name: split range
+//!     on_error: null_targets
+//!     op:
+//!       split:
+//!         item: range
+//!         splitter:
+//!           separator_char: { sep_char: "-" }
+//!         left: { type: i32, idx: 1, name: from }
+//!         right: { type: i32, idx: 2, name: to }
+//!         delete_source_item: true
+//! ```
+//!
+//! Configs are checked completely when they are loaded. Errors are reported as
+//! `VenumTdsTransRichError::Config`, located by the path to the offending field, e.g.
+//! `steps[0].op.split.splitter.regex_pair.regex`.
+
+use std::{fmt, fs, path::Path, str::FromStr};
+
+use regex::Regex;
+use serde::{
+    de::{self, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use venum::venum::Value;
+use venum_tds::{cell::DataCell, row::DataCellRow, traits::VDataContainer};
+
+use crate::{
+    container::{
+        AddItem, CastItem, CompactItemIdxs, ConvertedTarget, CopyItem, DeleteItemAtIdx,
+        ForEachItem, MatchedItem, MergeItemsAs, MergeNItemsAs, MoveItem, MutateItemIdx, OnConflict,
+        RenameItem, ReorderItems, SplitDateTimeItemAtIdx, SplitItemAtIdx, SplitItemAtIdxByKey,
+        SplitItemAtIdxN, SplitItemAtIdxNamed, SwapItems, TargetConflict, TargetItem, UnlistedItems,
+    },
+    errors::{Result, VenumTdsTransRichError},
+    pipeline::{Pipeline, PipelineStep, StepErrorPolicy},
+    selector::ItemSelector,
+    traits::{
+        container::TransrichContainerInplace,
+        item::{CastMode, SplitNTargetMismatch, UnknownKeys},
+        value::{Merge, MergeN, Split, SplitKeyed, SplitN, SplitNNamed},
+    },
+    value_conversion::ConversionOptions,
+    value_merging::{MergeNone, ValueStringConcatMerge, ValueStringJoinMerge, ValueTemplateMerge},
+    value_splitting::{
+        DateTimeComponent, DuplicateKeys, EmptyPolicy, FixedWidthShortRecord, FixedWidthUnit,
+        MaxSplits, Separator, SeparatorQuoting, SplitPosition, Stringifying, TokenPolicy,
+        UrlComponent, ValueDateTimeSplitN, ValueNumberFractionSplit, ValueStringFixedWidthSplitN,
+        ValueStringJsonSplitN, ValueStringKeyValueSplit, ValueStringRegexPairSplit,
+        ValueStringRegexSplitN, ValueStringSeparatorCharQuotedSplit,
+        ValueStringSeparatorCharQuotedSplitN, ValueStringSeparatorCharSplit,
+        ValueStringSeparatorCharSplitN, ValueStringSeparatorPairSplit, ValueStringSeparatorSplitN,
+        ValueStringUrlSplitN, WithTokenPolicy,
+    },
+};
+
+type BoxedOp = Box<dyn TransrichContainerInplace<DataCellRow>>;
+
+fn config_err(location: String, msg: String) -> VenumTdsTransRichError {
+    VenumTdsTransRichError::Config { location, msg }
+}
+
+const TYPE_NAMES: &str = "char, string, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, \
+                          f64, bool, decimal, date, datetime, datetime_tz";
+
+/// A target type, written as a string like `"f32"`. Dates and times (`date`, `datetime` and
+/// `datetime_tz`) can carry a chrono format, e.g. `"date:%Y-%m-%d"`, which is tried first when a
+/// string is parsed into the target.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TypeDescr {
+    pub type_info: Value,
+    pub fmt: Option<String>,
+}
+
+impl TypeDescr {
+    /// `conversion` with the format of this type (if any) tried first.
+    pub fn conversion(&self, conversion: &ConversionOptions) -> ConversionOptions {
+        let mut conversion = conversion.clone();
+        match (&self.fmt, &self.type_info) {
+            (Some(fmt), Value::NaiveDate(_)) => conversion.date_formats.insert(0, fmt.clone()),
+            (Some(fmt), _) => conversion.date_time_formats.insert(0, fmt.clone()),
+            (None, _) => {}
+        }
+        conversion
+    }
+}
+
+impl FromStr for TypeDescr {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (name, fmt) = match s.split_once(':') {
+            Some((name, fmt)) => (name, Some(String::from(fmt))),
+            None => (s, None),
+        };
+        let type_info = match name {
+            "char" => Value::char_default(),
+            "string" => Value::string_default(),
+            "i8" => Value::int8_default(),
+            "i16" => Value::int16_default(),
+            "i32" => Value::int32_default(),
+            "i64" => Value::int64_default(),
+            "i128" => Value::Int128(0),
+            "u8" => Value::uint8_default(),
+            "u16" => Value::UInt16(0),
+            "u32" => Value::uint32_default(),
+            "u64" => Value::UInt64(0),
+            "u128" => Value::UInt128(0),
+            "f32" => Value::float32_default(),
+            "f64" => Value::float64_default(),
+            "bool" => Value::bool_default(),
+            "decimal" => Value::decimal_default(),
+            "date" => Value::naive_date_default(),
+            "datetime" => Value::naive_date_time_default(),
+            "datetime_tz" => Value::date_time_default(),
+            _ => {
+                return Err(format!(
+                    "unknown type '{}', expected one of: {}",
+                    name, TYPE_NAMES
+                ))
+            }
+        };
+        let is_temporal = matches!(
+            type_info,
+            Value::NaiveDate(_) | Value::NaiveDateTime(_) | Value::DateTime(_)
+        );
+        if fmt.is_some() && !is_temporal {
+            return Err(format!("type '{}' doesn't take a format", name));
+        }
+        Ok(TypeDescr { type_info, fmt })
+    }
+}
+
+impl TryFrom<String> for TypeDescr {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// A regex pattern, that is checked when the config is loaded. `{name}` and `{idx}` (see
+/// `for_each_item`) are replaced with an example name and idx for the check.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct RegexPattern(pub String);
+
+impl TryFrom<String> for RegexPattern {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        Regex::new(&s.replace("{name}", "name").replace("{idx}", "0"))
+            .map_err(|e| e.to_string())?;
+        Ok(RegexPattern(s))
+    }
+}
+
+/// A merge template (see `ValueTemplateMerge`), that is checked when the config is loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TemplatePattern(pub String);
+
+impl TryFrom<String> for TemplatePattern {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        ValueTemplateMerge::from(s.clone(), MergeNone::default()).map_err(|e| e.to_string())?;
+        Ok(TemplatePattern(s))
+    }
+}
+
+/// A path into a JSON document (see `ValueStringJsonSplitN`), that is checked when the config is
+/// loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct JsonPath(pub String);
+
+impl TryFrom<String> for JsonPath {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        ValueStringJsonSplitN::from(vec![s.clone()], false, false).map_err(|e| e.to_string())?;
+        Ok(JsonPath(s))
+    }
+}
+
+/// An `ItemSelector`: an item idx, an item name or a map with one of the keys of
+/// `SELECTOR_KEYS`. A `name_regex` is kept as pattern, as `{name}` and `{idx}` within
+/// `for_each_item` have to be replaced before it's compiled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectorConfig {
+    Selector(ItemSelector),
+    NameRegex(RegexPattern),
+}
+
+impl<'de> Deserialize<'de> for SelectorConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(SelectorConfigVisitor)
+    }
+}
+
+struct SelectorConfigVisitor;
+
+const SELECTOR_KEYS: &[&str] = &[
+    "idx",
+    "range",
+    "name",
+    "name_ignore_case",
+    "name_regex",
+    "type",
+];
+
+impl<'de> Visitor<'de> for SelectorConfigVisitor {
+    type Value = SelectorConfig;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an item idx, an item name or a map with one of the keys: ")?;
+        f.write_str(&SELECTOR_KEYS.join(", "))
+    }
+
+    fn visit_u64<E: de::Error>(self, idx: u64) -> std::result::Result<SelectorConfig, E> {
+        usize::try_from(idx)
+            .map(|idx| SelectorConfig::Selector(ItemSelector::Idx(idx)))
+            .map_err(|_| E::custom(format!("idx {} out of range", idx)))
+    }
+
+    fn visit_i64<E: de::Error>(self, idx: i64) -> std::result::Result<SelectorConfig, E> {
+        usize::try_from(idx)
+            .map(|idx| SelectorConfig::Selector(ItemSelector::Idx(idx)))
+            .map_err(|_| E::custom(format!("idx {} out of range", idx)))
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> std::result::Result<SelectorConfig, E> {
+        Ok(SelectorConfig::Selector(ItemSelector::Name(String::from(
+            name,
+        ))))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<SelectorConfig, A::Error> {
+        let key: String = map
+            .next_key()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let selector = match key.as_str() {
+            "idx" => SelectorConfig::Selector(ItemSelector::Idx(map.next_value()?)),
+            "range" => {
+                let [start, end]: [usize; 2] = map.next_value()?;
+                SelectorConfig::Selector(ItemSelector::IdxRange(start..end))
+            }
+            "name" => SelectorConfig::Selector(ItemSelector::Name(map.next_value()?)),
+            "name_ignore_case" => {
+                SelectorConfig::Selector(ItemSelector::NameIgnoreCase(map.next_value()?))
+            }
+            "name_regex" => SelectorConfig::NameRegex(map.next_value()?),
+            "type" => SelectorConfig::Selector(ItemSelector::TypeInfo(
+                map.next_value::<TypeDescr>()?.type_info,
+            )),
+            _ => return Err(de::Error::unknown_field(&key, SELECTOR_KEYS)),
+        };
+        if map.next_key::<String>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(selector)
+    }
+}
+
+/// A new item: `{ type: .., idx: .., name: .. }`. Within `for_each_item`, `idx` is an offset to
+/// the first free idx and `{name}`/`{idx}` in `name` refer to the matched item (see `MatchedItem`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TargetConfig {
+    #[serde(rename = "type")]
+    pub type_info: TypeDescr,
+    pub idx: usize,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DateTimeTargetConfig {
+    pub component: DateTimeComponent,
+    pub target: TargetConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamedTargetConfig {
+    pub token: String,
+    pub target: TargetConfig,
+}
+
+/// The target of a key. `default` (a string, converted like the value would be) is used, if the
+/// key is missing.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyTargetConfig {
+    pub key: String,
+    #[serde(default)]
+    pub default: Option<String>,
+    pub target: TargetConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum UnknownKeysConfig {
+    #[default]
+    Ignore,
+    Fail,
+    CollectInto(TargetConfig),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeparatorConfig {
+    Char(char),
+    Str(String),
+    Regex(RegexPattern),
+}
+
+impl SeparatorConfig {
+    fn build(&self) -> Result<Separator> {
+        match self {
+            SeparatorConfig::Char(c) => Ok(Separator::Char(*c)),
+            SeparatorConfig::Str(s) => Ok(Separator::Str(s.clone())),
+            SeparatorConfig::Regex(re) => Separator::regex(re.0.clone()),
+        }
+    }
+}
+
+/// `EmptyPolicy`, with the default value given as string.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyPolicyConfig {
+    #[default]
+    Keep,
+    AsNone,
+    Fail,
+    Default(String),
+}
+
+impl EmptyPolicyConfig {
+    fn build(&self) -> EmptyPolicy {
+        match self {
+            EmptyPolicyConfig::Keep => EmptyPolicy::Keep,
+            EmptyPolicyConfig::AsNone => EmptyPolicy::AsNone,
+            EmptyPolicyConfig::Fail => EmptyPolicy::Fail,
+            EmptyPolicyConfig::Default(s) => EmptyPolicy::Default(Value::String(s.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TokenPolicyConfig {
+    pub empty_src: EmptyPolicyConfig,
+    pub empty_token: EmptyPolicyConfig,
+    pub trim: bool,
+}
+
+impl TokenPolicyConfig {
+    fn build(&self) -> TokenPolicy {
+        TokenPolicy {
+            empty_src: self.empty_src.build(),
+            empty_token: self.empty_token.build(),
+            trim: self.trim,
+        }
+    }
+}
+
+/// The splitters implementing `Split`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum SplitConfig {
+    SeparatorChar {
+        sep_char: char,
+        #[serde(default)]
+        split_none: bool,
+    },
+    RegexPair {
+        regex: RegexPattern,
+        #[serde(default)]
+        split_none: bool,
+    },
+    SeparatorPair {
+        separator: SeparatorConfig,
+        #[serde(default)]
+        position: SplitPosition,
+        #[serde(default)]
+        split_none: bool,
+    },
+    SeparatorCharQuoted {
+        sep_char: char,
+        #[serde(default)]
+        quoting: SeparatorQuoting,
+        #[serde(default)]
+        split_none: bool,
+    },
+    NumberFraction {
+        #[serde(default)]
+        split_none: bool,
+    },
+    Stringifying(Box<SplitConfig>),
+    WithTokenPolicy {
+        splitter: Box<SplitConfig>,
+        policy: TokenPolicyConfig,
+    },
+}
+
+impl SplitConfig {
+    pub fn build(&self) -> Result<Box<dyn Split>> {
+        Ok(match self {
+            SplitConfig::SeparatorChar {
+                sep_char,
+                split_none,
+            } => Box::new(ValueStringSeparatorCharSplit {
+                sep_char: *sep_char,
+                split_none: *split_none,
+            }),
+            SplitConfig::RegexPair { regex, split_none } => Box::new(
+                ValueStringRegexPairSplit::from(regex.0.clone(), *split_none)?,
+            ),
+            SplitConfig::SeparatorPair {
+                separator,
+                position,
+                split_none,
+            } => Box::new(ValueStringSeparatorPairSplit {
+                separator: separator.build()?,
+                position: *position,
+                split_none: *split_none,
+            }),
+            SplitConfig::SeparatorCharQuoted {
+                sep_char,
+                quoting,
+                split_none,
+            } => Box::new(ValueStringSeparatorCharQuotedSplit {
+                sep_char: *sep_char,
+                quoting: quoting.clone(),
+                split_none: *split_none,
+            }),
+            SplitConfig::NumberFraction { split_none } => Box::new(ValueNumberFractionSplit {
+                split_none: *split_none,
+            }),
+            SplitConfig::Stringifying(splitter) => Box::new(Stringifying(splitter.build()?)),
+            SplitConfig::WithTokenPolicy { splitter, policy } => Box::new(WithTokenPolicy {
+                splitter: splitter.build()?,
+                policy: policy.build(),
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixedWidthColumns {
+    Widths(Vec<usize>),
+    /// `[start, end)` pairs
+    Ranges(Vec<[usize; 2]>),
+}
+
+/// The splitters implementing `SplitN`. `regex`, `json`, `url` and `date_time` also implement
+/// `SplitNNamed` and can be used with `split_named`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum SplitNConfig {
+    SeparatorChar {
+        sep_char: char,
+        #[serde(default)]
+        split_none: bool,
+        #[serde(default)]
+        split_none_into_num_clones: Option<usize>,
+    },
+    Regex {
+        regex: RegexPattern,
+        #[serde(default)]
+        split_none: bool,
+    },
+    Separator {
+        separator: SeparatorConfig,
+        #[serde(default)]
+        max_splits: MaxSplits,
+        #[serde(default)]
+        split_none: bool,
+        #[serde(default)]
+        split_none_into_num_clones: Option<usize>,
+    },
+    FixedWidth {
+        columns: FixedWidthColumns,
+        #[serde(default)]
+        unit: FixedWidthUnit,
+        #[serde(default)]
+        trim: bool,
+        #[serde(default)]
+        blank_as_none: bool,
+        #[serde(default)]
+        short_record: FixedWidthShortRecord,
+        #[serde(default)]
+        split_none: bool,
+    },
+    SeparatorCharQuoted {
+        sep_char: char,
+        #[serde(default)]
+        quoting: SeparatorQuoting,
+        #[serde(default)]
+        split_none: bool,
+        #[serde(default)]
+        split_none_into_num_clones: Option<usize>,
+    },
+    Json {
+        paths: Vec<JsonPath>,
+        #[serde(default)]
+        missing_as_none: bool,
+        #[serde(default)]
+        split_none: bool,
+    },
+    Url {
+        components: Vec<UrlComponent>,
+        #[serde(default)]
+        split_none: bool,
+    },
+    DateTime {
+        components: Vec<DateTimeComponent>,
+        #[serde(default)]
+        parse_fmt: Option<String>,
+        #[serde(default)]
+        split_none: bool,
+    },
+    Stringifying(Box<SplitNConfig>),
+    WithTokenPolicy {
+        splitter: Box<SplitNConfig>,
+        policy: TokenPolicyConfig,
+    },
+}
+
+impl SplitNConfig {
+    pub fn build(&self) -> Result<Box<dyn SplitN>> {
+        Ok(match self {
+            SplitNConfig::SeparatorChar {
+                sep_char,
+                split_none,
+                split_none_into_num_clones,
+            } => Box::new(ValueStringSeparatorCharSplitN {
+                sep_char: *sep_char,
+                split_none: *split_none,
+                split_none_into_num_clones: *split_none_into_num_clones,
+            }),
+            SplitNConfig::Separator {
+                separator,
+                max_splits,
+                split_none,
+                split_none_into_num_clones,
+            } => Box::new(ValueStringSeparatorSplitN {
+                separator: separator.build()?,
+                max_splits: *max_splits,
+                split_none: *split_none,
+                split_none_into_num_clones: *split_none_into_num_clones,
+            }),
+            SplitNConfig::FixedWidth {
+                columns,
+                unit,
+                trim,
+                blank_as_none,
+                short_record,
+                split_none,
+            } => Box::new(ValueStringFixedWidthSplitN {
+                ranges: match columns {
+                    FixedWidthColumns::Widths(widths) => {
+                        ValueStringFixedWidthSplitN::ranges_from_widths(widths)
+                    }
+                    FixedWidthColumns::Ranges(ranges) => {
+                        ranges.iter().map(|[start, end]| *start..*end).collect()
+                    }
+                },
+                unit: *unit,
+                trim: *trim,
+                blank_as_none: *blank_as_none,
+                short_record: *short_record,
+                split_none: *split_none,
+            }),
+            SplitNConfig::SeparatorCharQuoted {
+                sep_char,
+                quoting,
+                split_none,
+                split_none_into_num_clones,
+            } => Box::new(ValueStringSeparatorCharQuotedSplitN {
+                sep_char: *sep_char,
+                quoting: quoting.clone(),
+                split_none: *split_none,
+                split_none_into_num_clones: *split_none_into_num_clones,
+            }),
+            SplitNConfig::Stringifying(splitter) => Box::new(Stringifying(splitter.build()?)),
+            SplitNConfig::WithTokenPolicy { splitter, policy } => Box::new(WithTokenPolicy {
+                splitter: splitter.build()?,
+                policy: policy.build(),
+            }),
+            // named splitters (boxed once more, as a `SplitN`)
+            named => Box::new(named.build_named()?),
+        })
+    }
+
+    /// Like `build`, but fails for splitters that don't implement `SplitNNamed`.
+    pub fn build_named(&self) -> Result<Box<dyn SplitNNamed>> {
+        Ok(match self {
+            SplitNConfig::Regex { regex, split_none } => {
+                Box::new(ValueStringRegexSplitN::from(regex.0.clone(), *split_none)?)
+            }
+            SplitNConfig::Json {
+                paths,
+                missing_as_none,
+                split_none,
+            } => Box::new(ValueStringJsonSplitN::from(
+                paths.iter().map(|path| path.0.clone()).collect(),
+                *missing_as_none,
+                *split_none,
+            )?),
+            SplitNConfig::Url {
+                components,
+                split_none,
+            } => Box::new(ValueStringUrlSplitN {
+                components: components.clone(),
+                split_none: *split_none,
+            }),
+            SplitNConfig::DateTime {
+                components,
+                parse_fmt,
+                split_none,
+            } => Box::new(ValueDateTimeSplitN {
+                components: components.clone(),
+                parse_fmt: parse_fmt.clone(),
+                split_none: *split_none,
+            }),
+            SplitNConfig::Stringifying(splitter) => Box::new(Stringifying(splitter.build_named()?)),
+            SplitNConfig::WithTokenPolicy { splitter, policy } => Box::new(WithTokenPolicy {
+                splitter: splitter.build_named()?,
+                policy: policy.build(),
+            }),
+            _ => {
+                return Err(VenumTdsTransRichError::Generic {
+                    msg: String::from(
+                        "splitter has no token names, use regex, json, url or date_time",
+                    ),
+                })
+            }
+        })
+    }
+}
+
+/// The splitters implementing `SplitKeyed`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum SplitKeyedConfig {
+    KeyValue {
+        pair_sep: char,
+        kv_sep: char,
+        #[serde(default)]
+        quoting: Option<SeparatorQuoting>,
+        #[serde(default)]
+        duplicate_keys: DuplicateKeys,
+        #[serde(default)]
+        split_none: bool,
+    },
+    Stringifying(Box<SplitKeyedConfig>),
+    WithTokenPolicy {
+        splitter: Box<SplitKeyedConfig>,
+        policy: TokenPolicyConfig,
+    },
+}
+
+impl SplitKeyedConfig {
+    pub fn build(&self) -> Result<Box<dyn SplitKeyed>> {
+        Ok(match self {
+            SplitKeyedConfig::KeyValue {
+                pair_sep,
+                kv_sep,
+                quoting,
+                duplicate_keys,
+                split_none,
+            } => Box::new(ValueStringKeyValueSplit {
+                pair_sep: *pair_sep,
+                kv_sep: *kv_sep,
+                quoting: quoting.clone(),
+                duplicate_keys: *duplicate_keys,
+                split_none: *split_none,
+            }),
+            SplitKeyedConfig::Stringifying(splitter) => Box::new(Stringifying(splitter.build()?)),
+            SplitKeyedConfig::WithTokenPolicy { splitter, policy } => Box::new(WithTokenPolicy {
+                splitter: splitter.build()?,
+                policy: policy.build(),
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum MergeConfig {
+    Concat {
+        #[serde(default)]
+        merge_none: MergeNone,
+    },
+    Join {
+        separator: String,
+        #[serde(default)]
+        merge_none: MergeNone,
+    },
+    Template {
+        template: TemplatePattern,
+        #[serde(default)]
+        merge_none: MergeNone,
+    },
+}
+
+impl MergeConfig {
+    pub fn build(&self) -> Result<Box<dyn Merge>> {
+        Ok(match self {
+            MergeConfig::Concat { merge_none } => Box::new(ValueStringConcatMerge {
+                merge_none: *merge_none,
+            }),
+            MergeConfig::Join {
+                separator,
+                merge_none,
+            } => Box::new(ValueStringJoinMerge {
+                separator: separator.clone(),
+                merge_none: *merge_none,
+            }),
+            MergeConfig::Template {
+                template,
+                merge_none,
+            } => Box::new(ValueTemplateMerge::from(template.0.clone(), *merge_none)?),
+        })
+    }
+
+    pub fn build_n(&self) -> Result<Box<dyn MergeN>> {
+        Ok(match self {
+            MergeConfig::Concat { merge_none } => Box::new(ValueStringConcatMerge {
+                merge_none: *merge_none,
+            }),
+            MergeConfig::Join {
+                separator,
+                merge_none,
+            } => Box::new(ValueStringJoinMerge {
+                separator: separator.clone(),
+                merge_none: *merge_none,
+            }),
+            MergeConfig::Template {
+                template,
+                merge_none,
+            } => Box::new(ValueTemplateMerge::from(template.0.clone(), *merge_none)?),
+        })
+    }
+}
+
+/// The ops of `container.rs`. Fields not given default to `false`, `None`, `TargetConflict::Fail`,
+/// `CastMode::Strict`, `SplitNTargetMismatch::Fail`, `UnlistedItems::Fail`, ignoring unknown keys
+/// and the default `ConversionOptions` (which apply to all targets of the op).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum OpConfig {
+    MutateItemIdx {
+        item: SelectorConfig,
+        to: usize,
+    },
+    DeleteItem {
+        item: SelectorConfig,
+    },
+    /// Adds an item without data.
+    AddItem {
+        target: TargetConfig,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+    },
+    RenameItem {
+        item: SelectorConfig,
+        name: String,
+    },
+    CastItem {
+        item: SelectorConfig,
+        #[serde(rename = "type")]
+        type_info: TypeDescr,
+        #[serde(default)]
+        mode: CastMode,
+        #[serde(default)]
+        conversion: ConversionOptions,
+    },
+    CopyItem {
+        item: SelectorConfig,
+        idx: usize,
+        name: String,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+    },
+    MoveItem {
+        item: SelectorConfig,
+        to: usize,
+    },
+    SwapItems {
+        a: SelectorConfig,
+        b: SelectorConfig,
+    },
+    ReorderItems {
+        names: Vec<String>,
+        #[serde(default)]
+        unlisted: UnlistedItems,
+    },
+    CompactItemIdxs,
+    Split {
+        item: SelectorConfig,
+        splitter: SplitConfig,
+        left: TargetConfig,
+        right: TargetConfig,
+        #[serde(default)]
+        conversion: ConversionOptions,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+        #[serde(default)]
+        delete_source_item: bool,
+    },
+    SplitN {
+        item: SelectorConfig,
+        splitter: SplitNConfig,
+        targets: Vec<TargetConfig>,
+        #[serde(default)]
+        on_mismatch: SplitNTargetMismatch,
+        #[serde(default)]
+        conversion: ConversionOptions,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+        #[serde(default)]
+        delete_source_item: bool,
+    },
+    SplitDateTime {
+        item: SelectorConfig,
+        targets: Vec<DateTimeTargetConfig>,
+        #[serde(default)]
+        parse_fmt: Option<String>,
+        #[serde(default)]
+        split_none: bool,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+        #[serde(default)]
+        delete_source_item: bool,
+    },
+    SplitNamed {
+        item: SelectorConfig,
+        splitter: SplitNConfig,
+        targets: Vec<NamedTargetConfig>,
+        #[serde(default)]
+        conversion: ConversionOptions,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+        #[serde(default)]
+        delete_source_item: bool,
+    },
+    SplitByKey {
+        item: SelectorConfig,
+        splitter: SplitKeyedConfig,
+        targets: Vec<KeyTargetConfig>,
+        #[serde(default)]
+        unknown_keys: UnknownKeysConfig,
+        #[serde(default)]
+        conversion: ConversionOptions,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+        #[serde(default)]
+        delete_source_item: bool,
+    },
+    Merge {
+        a: SelectorConfig,
+        b: SelectorConfig,
+        merger: MergeConfig,
+        target: TargetConfig,
+        #[serde(default)]
+        conversion: ConversionOptions,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+        #[serde(default)]
+        delete_source_items: bool,
+    },
+    MergeN {
+        items: Vec<SelectorConfig>,
+        merger: MergeConfig,
+        target: TargetConfig,
+        #[serde(default)]
+        conversion: ConversionOptions,
+        #[serde(default)]
+        on_conflict: TargetConflict,
+        #[serde(default)]
+        delete_source_items: bool,
+    },
+    /// Applies `op` to every item matched by `items`. Within `op`, `{name}` and `{idx}` in item
+    /// names and target names refer to the matched item, and target idx are offsets to the first
+    /// free idx.
+    ForEachItem {
+        items: SelectorConfig,
+        op: Box<OpConfig>,
+    },
+}
+
+/// What the op is built for: the whole container, or an item matched by `for_each_item`.
+struct BuildCtx<'a> {
+    matched: Option<&'a MatchedItem>,
+}
+
+impl BuildCtx<'_> {
+    fn selector(&self, selector: &SelectorConfig) -> Result<ItemSelector> {
+        let selector = match selector {
+            SelectorConfig::Selector(selector) => selector,
+            SelectorConfig::NameRegex(pattern) => {
+                return ItemSelector::name_regex(match self.matched {
+                    Some(m) => pattern
+                        .0
+                        .replace("{name}", &regex::escape(&m.name))
+                        .replace("{idx}", &m.idx.to_string()),
+                    None => pattern.0.clone(),
+                })
+            }
+        };
+        let m = match self.matched {
+            Some(m) => m,
+            None => return Ok(selector.clone()),
+        };
+        Ok(match selector {
+            ItemSelector::Name(name) => ItemSelector::Name(m.name_from(name)),
+            ItemSelector::NameIgnoreCase(name) => ItemSelector::NameIgnoreCase(m.name_from(name)),
+            other => other.clone(),
+        })
+    }
+
+    fn selectors(&self, selectors: &[SelectorConfig]) -> Result<Vec<ItemSelector>> {
+        selectors.iter().map(|s| self.selector(s)).collect()
+    }
+
+    fn target(&self, target: &TargetConfig) -> TargetItem {
+        match self.matched {
+            Some(m) => m.target(target.type_info.type_info.clone(), &target.name, target.idx),
+            None => (
+                target.type_info.type_info.clone(),
+                target.idx,
+                target.name.clone(),
+            ),
+        }
+    }
+
+    /// The target with the conversion options of the op (and the format of its type), if they
+    /// aren't the default ones.
+    fn converted_target(
+        &self,
+        target: &TargetConfig,
+        conversion: &ConversionOptions,
+    ) -> ConvertedTarget {
+        let conversion = target.type_info.conversion(conversion);
+        ConvertedTarget {
+            item: self.target(target),
+            conversion: (conversion != ConversionOptions::default()).then_some(conversion),
+        }
+    }
+}
+
+/// An op that always fails, in place of an op that couldn't be built for an item.
+struct FailingOp(VenumTdsTransRichError);
+
+impl<C: VDataContainer> TransrichContainerInplace<C> for FailingOp {
+    fn apply(&self, _container: &mut C) -> Result<()> {
+        Err(self.0.clone())
+    }
+}
+
+impl OpConfig {
+    pub fn build(&self) -> Result<BoxedOp> {
+        self.build_with(&BuildCtx { matched: None })
+    }
+
+    fn build_with(&self, ctx: &BuildCtx) -> Result<BoxedOp> {
+        Ok(match self {
+            OpConfig::MutateItemIdx { item, to } => Box::new(MutateItemIdx {
+                from: ctx.selector(item)?,
+                to: *to,
+            }),
+            OpConfig::DeleteItem { item } => Box::new(DeleteItemAtIdx(ctx.selector(item)?)),
+            OpConfig::AddItem {
+                target,
+                on_conflict,
+            } => {
+                let (type_info, idx, name) = ctx.target(target);
+                Box::new(AddItem::with_conflict(
+                    DataCell::new_without_data(type_info, name, idx),
+                    *on_conflict,
+                ))
+            }
+            OpConfig::RenameItem { item, name } => Box::new(RenameItem {
+                idx: ctx.selector(item)?,
+                name: ctx
+                    .matched
+                    .map_or_else(|| name.clone(), |m| m.name_from(name)),
+            }),
+            OpConfig::CastItem {
+                item,
+                type_info,
+                mode,
+                conversion,
+            } => Box::new(CastItem {
+                idx: ctx.selector(item)?,
+                type_info: type_info.type_info.clone(),
+                mode: *mode,
+                conversion: type_info.conversion(conversion),
+            }),
+            OpConfig::CopyItem {
+                item,
+                idx,
+                name,
+                on_conflict,
+            } => Box::new(OnConflict {
+                op: CopyItem {
+                    from: ctx.selector(item)?,
+                    to: match ctx.matched {
+                        Some(m) => (m.next_free_idx + idx, m.name_from(name)),
+                        None => (*idx, name.clone()),
+                    },
+                },
+                on_conflict: *on_conflict,
+            }),
+            OpConfig::MoveItem { item, to } => Box::new(MoveItem {
+                from: ctx.selector(item)?,
+                to: *to,
+            }),
+            OpConfig::SwapItems { a, b } => Box::new(SwapItems {
+                a: ctx.selector(a)?,
+                b: ctx.selector(b)?,
+            }),
+            OpConfig::ReorderItems { names, unlisted } => Box::new(ReorderItems {
+                names: names.clone(),
+                unlisted: *unlisted,
+            }),
+            OpConfig::CompactItemIdxs => Box::new(CompactItemIdxs),
+            OpConfig::Split {
+                item,
+                splitter,
+                left,
+                right,
+                conversion,
+                on_conflict,
+                delete_source_item,
+            } => Box::new(OnConflict {
+                op: SplitItemAtIdx {
+                    idx: ctx.selector(item)?,
+                    divider: splitter.build()?,
+                    target_left: ctx.converted_target(left, conversion),
+                    target_right: ctx.converted_target(right, conversion),
+                    delete_source_item: *delete_source_item,
+                },
+                on_conflict: *on_conflict,
+            }),
+            OpConfig::SplitN {
+                item,
+                splitter,
+                targets,
+                on_mismatch,
+                conversion,
+                on_conflict,
+                delete_source_item,
+            } => Box::new(OnConflict {
+                op: SplitItemAtIdxN {
+                    idx: ctx.selector(item)?,
+                    divider: splitter.build()?,
+                    targets: targets
+                        .iter()
+                        .map(|t| ctx.converted_target(t, conversion))
+                        .collect(),
+                    on_mismatch: on_mismatch.clone(),
+                    delete_source_item: *delete_source_item,
+                },
+                on_conflict: *on_conflict,
+            }),
+            OpConfig::SplitDateTime {
+                item,
+                targets,
+                parse_fmt,
+                split_none,
+                on_conflict,
+                delete_source_item,
+            } => Box::new(OnConflict {
+                op: SplitDateTimeItemAtIdx {
+                    idx: ctx.selector(item)?,
+                    targets: targets
+                        .iter()
+                        .map(|t| (t.component.clone(), ctx.target(&t.target)))
+                        .collect(),
+                    parse_fmt: parse_fmt.clone(),
+                    split_none: *split_none,
+                    delete_source_item: *delete_source_item,
+                },
+                on_conflict: *on_conflict,
+            }),
+            OpConfig::SplitNamed {
+                item,
+                splitter,
+                targets: named_targets,
+                conversion,
+                on_conflict,
+                delete_source_item,
+            } => Box::new(OnConflict {
+                op: SplitItemAtIdxNamed {
+                    idx: ctx.selector(item)?,
+                    divider: splitter.build_named()?,
+                    targets: named_targets
+                        .iter()
+                        .map(|t| (t.token.clone(), ctx.converted_target(&t.target, conversion)))
+                        .collect(),
+                    delete_source_item: *delete_source_item,
+                },
+                on_conflict: *on_conflict,
+            }),
+            OpConfig::SplitByKey {
+                item,
+                splitter,
+                targets: key_targets,
+                unknown_keys,
+                conversion,
+                on_conflict,
+                delete_source_item,
+            } => Box::new(OnConflict {
+                op: SplitItemAtIdxByKey {
+                    idx: ctx.selector(item)?,
+                    divider: splitter.build()?,
+                    targets: key_targets
+                        .iter()
+                        .map(|t| {
+                            (
+                                t.key.clone(),
+                                t.default.clone().map(Value::String),
+                                ctx.converted_target(&t.target, conversion),
+                            )
+                        })
+                        .collect(),
+                    unknown_keys: match unknown_keys {
+                        UnknownKeysConfig::Ignore => UnknownKeys::Ignore,
+                        UnknownKeysConfig::Fail => UnknownKeys::Fail,
+                        UnknownKeysConfig::CollectInto(target) => {
+                            UnknownKeys::CollectInto(ctx.converted_target(target, conversion))
+                        }
+                    },
+                    delete_source_item: *delete_source_item,
+                },
+                on_conflict: *on_conflict,
+            }),
+            OpConfig::Merge {
+                a,
+                b,
+                merger,
+                target,
+                conversion,
+                on_conflict,
+                delete_source_items,
+            } => Box::new(OnConflict {
+                op: MergeItemsAs {
+                    idx_a: ctx.selector(a)?,
+                    idx_b: ctx.selector(b)?,
+                    merger: merger.build()?,
+                    target: ctx.converted_target(target, conversion),
+                    delete_source_items: *delete_source_items,
+                },
+                on_conflict: *on_conflict,
+            }),
+            OpConfig::MergeN {
+                items,
+                merger,
+                target,
+                conversion,
+                on_conflict,
+                delete_source_items,
+            } => Box::new(OnConflict {
+                op: MergeNItemsAs {
+                    idxs: ctx.selectors(items)?,
+                    merger: merger.build_n()?,
+                    target: ctx.converted_target(target, conversion),
+                    delete_source_items: *delete_source_items,
+                },
+                on_conflict: *on_conflict,
+            }),
+            OpConfig::ForEachItem { items, op } => {
+                // build once up front, so that a broken op fails when the config is loaded
+                op.build_with(&BuildCtx {
+                    matched: Some(&MatchedItem {
+                        idx: 0,
+                        name: String::new(),
+                        next_free_idx: 0,
+                    }),
+                })?;
+                let op = op.as_ref().clone();
+                Box::new(ForEachItem {
+                    selector: ctx.selector(items)?,
+                    op_for: move |m: &MatchedItem| -> BoxedOp {
+                        op.build_with(&BuildCtx { matched: Some(m) })
+                            .unwrap_or_else(|e| Box::new(FailingOp(e)))
+                    },
+                })
+            }
+        })
+    }
+}
+
+/// What to do, if the op of a step fails. See `StepErrorPolicy`. `null_targets` nulls out the
+/// targets of the op, so it's only available for ops with targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnErrorConfig {
+    #[default]
+    FailRow,
+    SkipStep,
+    NullTargets,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StepConfig {
+    /// Defaults to `step <n>`, where n counts from 0.
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub on_error: OnErrorConfig,
+    pub op: OpConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// The format by file extension: `json`, `yaml`/`yml` or `toml`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(ConfigFormat::Json),
+            Some("yaml") | Some("yml") => Ok(ConfigFormat::Yaml),
+            Some("toml") => Ok(ConfigFormat::Toml),
+            _ => Err(config_err(
+                path.display().to_string(),
+                String::from("unknown config format, expected a .json, .yaml, .yml or .toml file"),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConfig {
+    pub steps: Vec<StepConfig>,
+}
+
+fn located<E: fmt::Display>(e: serde_path_to_error::Error<E>) -> VenumTdsTransRichError {
+    config_err(e.path().to_string(), e.inner().to_string())
+}
+
+impl PipelineConfig {
+    pub fn parse(s: &str, format: ConfigFormat) -> Result<Self> {
+        match format {
+            ConfigFormat::Json => {
+                let mut de = serde_json::Deserializer::from_str(s);
+                let config = serde_path_to_error::deserialize(&mut de).map_err(located)?;
+                de.end()
+                    .map_err(|e| config_err(String::from("."), e.to_string()))?;
+                Ok(config)
+            }
+            ConfigFormat::Yaml => {
+                // serde_yaml_ng writes enums as tags (`!split`), ops are written as maps (`split: ..`)
+                let mut track = serde_path_to_error::Track::new();
+                let de = serde_path_to_error::Deserializer::new(
+                    serde_yaml_ng::Deserializer::from_str(s),
+                    &mut track,
+                );
+                serde_yaml_ng::with::singleton_map_recursive::deserialize(de)
+                    .map_err(|e| located(serde_path_to_error::Error::new(track.path(), e)))
+            }
+            ConfigFormat::Toml => {
+                serde_path_to_error::deserialize(toml::Deserializer::new(s)).map_err(located)
+            }
+        }
+    }
+
+    /// Builds a runnable pipeline, one step per configured step.
+    pub fn build(&self) -> Result<Pipeline<DataCellRow>> {
+        let mut pipeline = Pipeline::new();
+        for (i, step) in self.steps.iter().enumerate() {
+            let op = step
+                .op
+                .build()
+                .map_err(|e| config_err(format!("steps[{}].op", i), e.to_string()))?;
+            let on_error = match step.on_error {
+                OnErrorConfig::FailRow => StepErrorPolicy::FailRow,
+                OnErrorConfig::SkipStep => StepErrorPolicy::SkipStep,
+                OnErrorConfig::NullTargets if op.has_targets() => StepErrorPolicy::NullTargets,
+                OnErrorConfig::NullTargets => {
+                    return Err(config_err(
+                        format!("steps[{}].on_error", i),
+                        String::from("the op has no targets to null out"),
+                    ))
+                }
+            };
+            pipeline.steps.push(PipelineStep {
+                name: step.name.clone().unwrap_or_else(|| format!("step {}", i)),
+                op,
+                on_error,
+            });
+        }
+        Ok(pipeline)
+    }
+}
+
+/// Loads and builds the pipeline config at `path`. The format is taken from the file extension.
+pub fn load_pipeline(path: impl AsRef<Path>) -> Result<Pipeline<DataCellRow>> {
+    let path = path.as_ref();
+    let format = ConfigFormat::from_path(path)?;
+    let s = fs::read_to_string(path)
+        .map_err(|e| config_err(path.display().to_string(), e.to_string()))?;
+    PipelineConfig::parse(&s, format)?.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use venum_tds::traits::VDataContainerItem;
+
+    use crate::{pipeline::StepStatus, test_util::test_row, value_conversion::type_name};
+
+    use super::*;
+
+    fn data_of<'a>(c: &'a DataCellRow, name: &str) -> Option<&'a Value> {
+        c.0.iter()
+            .find(|dc| dc.get_name() == name)
+            .unwrap()
+            .get_data()
+    }
+
+    #[test]
+    fn test_type_descr() {
+        let t: TypeDescr = "f32".parse().unwrap();
+        assert_eq!(Value::float32_default(), t.type_info);
+        assert_eq!(None, t.fmt);
+
+        let t: TypeDescr = "date:%d.%m.%Y".parse().unwrap();
+        assert_eq!(Value::naive_date_default(), t.type_info);
+        assert_eq!(
+            vec![String::from("%d.%m.%Y")],
+            t.conversion(&ConversionOptions::default()).date_formats
+        );
+
+        assert_eq!(
+            Err(String::from("type 'i32' doesn't take a format")),
+            "i32:%Y".parse::<TypeDescr>()
+        );
+        assert!("float"
+            .parse::<TypeDescr>()
+            .unwrap_err()
+            .starts_with("unknown type 'float'"));
+
+        for name in TYPE_NAMES.split(", ") {
+            assert_eq!(
+                name,
+                type_name(&name.parse::<TypeDescr>().unwrap().type_info)
+            );
+        }
+    }
+
+    #[test]
+    fn test_yaml_pipeline() {
+        let config = r#"
+steps:
+  - name: split range
+    on_error: null_targets
+    op:
+      split:
+        item: range
+        splitter:
+          separator_char: { sep_char: "-" }
+        left: { type: i32, idx: 2, name: from }
+        right: { type: i32, idx: 3, name: to }
+        delete_source_item: true
+  - op:
+      split_n:
+        item: { name_ignore_case: DATES }
+        splitter:
+          separator: { separator: { str: " / " } }
+        targets:
+          - { type: "date:%d.%m.%Y", idx: 4, name: start }
+          - { type: "date:%d.%m.%Y", idx: 5, name: end }
+  - op: compact_item_idxs
+"#;
+        let pipeline = PipelineConfig::parse(config, ConfigFormat::Yaml)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!("step 1", pipeline.steps[1].name);
+
+        let mut c = test_row(&[
+            ("range", 0, Some("3-7")),
+            ("dates", 1, Some("01.02.2022 / 28.02.2022")),
+        ]);
+        let outcome = pipeline.apply_row(&mut c);
+        assert!(outcome.errors().is_empty());
+        assert_eq!(Some(&Value::Int32(7)), data_of(&c, "to"));
+        assert_eq!(
+            Some(&Value::NaiveDate(
+                chrono::NaiveDate::from_ymd_opt(2022, 2, 28).unwrap()
+            )),
+            data_of(&c, "end")
+        );
+        assert_eq!(
+            vec![0, 1, 2, 3, 4],
+            c.0.iter().map(|dc| dc.get_idx()).collect::<Vec<usize>>()
+        );
+
+        let mut c = test_row(&[
+            ("range", 0, Some("3")),
+            ("dates", 1, Some("01.02.2022 / 28.02.2022")),
+        ]);
+        let outcome = pipeline.apply_row(&mut c);
+        assert!(matches!(
+            outcome.steps[0].status,
+            StepStatus::TargetsNulled(_)
+        ));
+        assert_eq!(None, data_of(&c, "from"));
+        assert_eq!(None, data_of(&c, "to"));
+    }
+
+    #[test]
+    fn test_json_pipeline_merge_and_cast() {
+        let config = r#"{ "steps": [
+            { "op": { "cast_item": { "item": "qty", "type": "u16", "mode": "lenient" } } },
+            { "op": { "merge_n": {
+                "items": [{ "range": [1, 3] }],
+                "merger": { "join": { "separator": ", " } },
+                "target": { "type": "string", "idx": 3, "name": "address" },
+                "delete_source_items": true
+            } } }
+        ] }"#;
+        let pipeline = PipelineConfig::parse(config, ConfigFormat::Json)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut c = test_row(&[
+            ("qty", 0, Some("many")),
+            ("street", 1, Some("Main St 1")),
+            ("city", 2, Some("Springfield")),
+        ]);
+        assert!(!pipeline.apply_row(&mut c).is_failed());
+        assert_eq!(&Value::UInt16(0), c.get_by_idx(0).unwrap().get_type_info());
+        assert_eq!(None, data_of(&c, "qty"));
+        assert_eq!(
+            Some(&Value::from(String::from("Main St 1, Springfield"))),
+            data_of(&c, "address")
+        );
+    }
+
+    #[test]
+    fn test_toml_pipeline_for_each_item() {
+        let config = r#"
+[[steps]]
+name = "split ranges"
+
+[steps.op.for_each_item]
+items = { name_regex = "_range$" }
+op = { split = { item = "{name}", splitter = { separator_char = { sep_char = "-" } }, left = { type = "u8", idx = 0, name = "{name}_from" }, right = { type = "u8", idx = 1, name = "{name}_to" }, delete_source_item = true } }
+"#;
+        let pipeline = PipelineConfig::parse(config, ConfigFormat::Toml)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut c = test_row(&[
+            ("age_range", 0, Some("18-30")),
+            ("size_range", 1, Some("1-2")),
+        ]);
+        assert!(!pipeline.apply_row(&mut c).is_failed());
+        assert_eq!(Some(&Value::UInt8(30)), data_of(&c, "age_range_to"));
+        assert_eq!(Some(&Value::UInt8(1)), data_of(&c, "size_range_from"));
+        assert_eq!(4, c.0.len());
+    }
+
+    #[test]
+    fn test_for_each_item_name_regex_placeholders() {
+        let config = r#"
+steps:
+  - op:
+      for_each_item:
+        items: { name_regex: "^[ab]$" }
+        op: { delete_item: { item: { name_regex: "^{name}_.*$" } } }
+"#;
+        let pipeline = PipelineConfig::parse(config, ConfigFormat::Yaml)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut c = test_row(&[
+            ("a", 0, Some("1")),
+            ("a_raw", 1, Some("2")),
+            ("b", 2, Some("3")),
+            ("b_raw", 3, Some("4")),
+        ]);
+        assert!(!pipeline.apply_row(&mut c).is_failed());
+        let names: Vec<&str> = c.0.iter().map(|dc| dc.get_name()).collect();
+        assert_eq!(vec!["a", "b"], names);
+    }
+
+    #[test]
+    fn test_config_err_bad_regex_is_located() {
+        let config = r#"
+steps:
+  - op: compact_item_idxs
+  - op:
+      split:
+        item: 0
+        splitter:
+          regex_pair: { regex: "([a-z]" }
+        left: { type: string, idx: 1, name: a }
+        right: { type: string, idx: 2, name: b }
+"#;
+        match PipelineConfig::parse(config, ConfigFormat::Yaml) {
+            Err(VenumTdsTransRichError::Config { location, msg }) => {
+                assert_eq!("steps[1].op.split.splitter.regex_pair.regex", location);
+                assert!(msg.contains("unclosed group"), "{}", msg);
+            }
+            other => panic!("expected a config error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_config_err_bad_template_or_json_path_is_located() {
+        let config = r#"
+steps:
+  - op:
+      merge:
+        a: a
+        b: b
+        merger: { template: { template: "{0" } }
+        target: { type: string, idx: 2, name: ab }
+"#;
+        match PipelineConfig::parse(config, ConfigFormat::Yaml) {
+            Err(VenumTdsTransRichError::Config { location, msg }) => {
+                assert_eq!("steps[0].op.merge.merger.template.template", location);
+                assert!(msg.contains("unterminated placeholder"), "{}", msg);
+            }
+            other => panic!("expected a config error, got: {:?}", other),
+        }
+
+        let config = r#"
+steps:
+  - op:
+      split_n:
+        item: a
+        splitter: { json: { paths: [name, "items..0"] } }
+        targets: []
+"#;
+        match PipelineConfig::parse(config, ConfigFormat::Yaml) {
+            Err(VenumTdsTransRichError::Config { location, msg }) => {
+                assert_eq!("steps[0].op.split_n.splitter.json.paths[1]", location);
+                assert!(msg.contains("illegal path, empty segment"), "{}", msg);
+            }
+            other => panic!("expected a config error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_config_err_unknown_type_is_located() {
+        let config = r#"{ "steps": [
+            { "op": { "add_item": { "target": { "type": "float", "idx": 1, "name": "x" } } } }
+        ] }"#;
+        match PipelineConfig::parse(config, ConfigFormat::Json) {
+            Err(VenumTdsTransRichError::Config { location, msg }) => {
+                assert_eq!("steps[0].op.add_item.target.type", location);
+                assert!(msg.starts_with("unknown type 'float'"), "{}", msg);
+            }
+            other => panic!("expected a config error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_config_err_on_build_is_located() {
+        let config = r#"
+steps:
+  - op: { rename_item: { item: a, name: b } }
+  - op: { rename_item: { item: b, name: c } }
+    on_error: null_targets
+"#;
+        let res = PipelineConfig::parse(config, ConfigFormat::Yaml)
+            .unwrap()
+            .build();
+        assert!(matches!(
+            res,
+            Err(VenumTdsTransRichError::Config { location, .. }) if location == "steps[1].on_error"
+        ));
+
+        let config = r#"
+steps:
+  - op:
+      split_named:
+        item: a
+        splitter: { separator_char: { sep_char: "," } }
+        targets: []
+"#;
+        let res = PipelineConfig::parse(config, ConfigFormat::Yaml)
+            .unwrap()
+            .build();
+        assert!(matches!(
+            res,
+            Err(VenumTdsTransRichError::Config { location, .. }) if location == "steps[0].op"
+        ));
+
+        let config = r#"
+steps:
+  - op: { delete_item: { item: { name_regex: "^{name}$" } } }
+"#;
+        let res = PipelineConfig::parse(config, ConfigFormat::Yaml)
+            .unwrap()
+            .build()
+            .err();
+        match res {
+            Some(VenumTdsTransRichError::Config { location, msg }) => {
+                assert_eq!("steps[0].op", location);
+                assert!(msg.starts_with("NameRegex(^{name}$): "), "{}", msg);
+            }
+            other => panic!("expected a config error, got: {:?}", other),
+        }
+    }
+}
@@ -1,27 +1,42 @@
+use serde::Deserialize;
 use venum::venum::Value;
 use venum_tds::traits::{VDataContainer, VDataContainerItem};
 
 use crate::{
     errors::{ContainerOpsErrors, Result, VenumTdsTransRichError},
-    traits::{container::TransrichContainerInplace, item::SplitUsing, value::Split},
+    selector::{ItemSelector, SelectItems},
+    traits::{
+        container::{
+            NewItems, TransrichContainerInplace, TransrichContainerItems, TransrichItemsProducer,
+        },
+        item::{
+            CastMode, CastTo, MergeNUsing, MergeUsing, SplitKeyedUsing, SplitNTargetMismatch,
+            SplitNUsing, SplitUsing, UnknownKeys,
+        },
+        value::{Merge, MergeN, Split, SplitKeyed, SplitN, SplitNNamed},
+    },
+    value_conversion::{check_convertible, type_name, ConversionOptions, TargetConversions},
+    value_splitting::{DateTimeComponent, ValueDateTimeSplitN},
 };
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct MutateItemIdx {
-    pub from: usize,
+pub struct MutateItemIdx<I = usize> {
+    pub from: I,
     pub to: usize,
 }
-impl MutateItemIdx {
-    pub fn new(from: usize, to: usize) -> Self {
+impl<I> MutateItemIdx<I> {
+    pub fn new(from: I, to: usize) -> Self {
         Self { from, to }
     }
 }
-impl<C> TransrichContainerInplace<C> for MutateItemIdx
+impl<C, I> TransrichContainerInplace<C> for MutateItemIdx<I>
 where
     C: VDataContainer,
+    I: SelectItems<C>,
 {
     fn apply(&self, data_container: &mut C) -> Result<()> {
-        let container_entry = data_container.get_by_idx_mut(self.from);
+        let from = self.from.resolve_one(data_container)?;
+        let container_entry = data_container.get_by_idx_mut(from);
         match container_entry {
             None => Err(VenumTdsTransRichError::ContainerOps(
                 ContainerOpsErrors::Generic {
@@ -37,481 +52,3065 @@ where
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct DeleteItemAtIdx(pub usize);
-impl<C> TransrichContainerInplace<C> for DeleteItemAtIdx
+/// Deletes all items selected by `0`.
+pub struct DeleteItemAtIdx<I = usize>(pub I);
+impl<C, I> TransrichContainerInplace<C> for DeleteItemAtIdx<I>
 where
     C: VDataContainer,
+    I: SelectItems<C>,
 {
     fn apply(&self, data_container: &mut C) -> Result<()> {
-        match data_container.del_by_idx(self.0) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(VenumTdsTransRichError::from(e)),
+        for idx in self.0.resolve(data_container)? {
+            if let Err(e) = data_container.del_by_idx(idx) {
+                return Err(VenumTdsTransRichError::from(e));
+            }
         }
+        Ok(())
     }
 }
 
 pub struct AddItem<T: VDataContainerItem>(pub T);
+impl<T: VDataContainerItem> AddItem<T> {
+    /// Adds `item` according to `on_conflict`, instead of as is.
+    pub fn with_conflict(item: T, on_conflict: TargetConflict) -> OnConflict<Self> {
+        OnConflict {
+            op: AddItem(item),
+            on_conflict,
+        }
+    }
+}
+impl<C, D> TransrichItemsProducer<C> for AddItem<D>
+where
+    D: VDataContainerItem + Clone + Default,
+    C: VDataContainer<ITEM = D>,
+{
+    fn produce(&self, _data_container: &C) -> Result<NewItems<D>> {
+        Ok(NewItems {
+            items: vec![self.0.clone()],
+            replaced: Vec::new(),
+        })
+    }
+}
 impl<C, D> TransrichContainerInplace<C> for AddItem<D>
 where
     D: VDataContainerItem + Clone + Default,
     C: VDataContainer<ITEM = D>,
 {
     fn apply(&self, data_container: &mut C) -> Result<()> {
-        data_container.add(self.0.clone());
+        add_items(data_container, self.produce(data_container)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+/// Renames the item at `idx`. With a plain idx it works on any `VDataContainer`; with an
+/// `ItemSelector` it also fails if another item already has the name, which is why the container
+/// has to list its items (`TransrichContainerItems`) then.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenameItem<I = usize> {
+    pub idx: I,
+    pub name: String,
+}
+impl<I> RenameItem<I> {
+    pub fn new(idx: I, name: &str) -> Self {
+        Self {
+            idx,
+            name: String::from(name),
+        }
+    }
+}
+fn rename_item<C: VDataContainer>(data_container: &mut C, idx: usize, name: &str) -> Result<()> {
+    let entry = data_container.get_by_idx_mut(idx).ok_or_else(|| {
+        VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::Generic {
+            msg: format!("No DataEntry with idx {}. Can't rename.", idx),
+        })
+    })?;
+    entry.set_name(name);
+    Ok(())
+}
+impl<C: VDataContainer> TransrichContainerInplace<C> for RenameItem<usize> {
+    fn apply(&self, data_container: &mut C) -> Result<()> {
+        rename_item(data_container, self.idx, &self.name)
+    }
+}
+impl<C: TransrichContainerItems> TransrichContainerInplace<C> for RenameItem<ItemSelector> {
+    fn apply(&self, data_container: &mut C) -> Result<()> {
+        let idx = self.idx.resolve_one(data_container)?;
+        if let Some(other) = data_container
+            .items()
+            .iter()
+            .find(|e| e.get_idx() != idx && e.get_name() == self.name)
+        {
+            return Err(conflict_err(
+                other,
+                format!("an item with the same name exists. Can't rename item at idx {idx}."),
+            ));
+        }
+        rename_item(data_container, idx, &self.name)
+    }
+}
+
+/// Changes the type_info of all items matched by the selector and converts their data. With
+/// `CastMode::Strict` a failing conversion fails the whole op and no item is modified, with
+/// `CastMode::Lenient` the data of the item is set to `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastItem<I = usize> {
+    pub idx: I,
+    pub type_info: Value,
+    pub mode: CastMode,
+    pub conversion: ConversionOptions,
+}
+impl<CONT, ENTRY, I> TransrichContainerInplace<CONT> for CastItem<I>
+where
+    ENTRY: VDataContainerItem + CastTo + Clone,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        let mut casted = Vec::new();
+        for idx in self.idx.resolve(container)? {
+            let mut entry = container.get_by_idx(idx).cloned().ok_or_else(|| {
+                VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::Generic {
+                    msg: format!("No DataEntry with idx {}. Can't cast.", idx),
+                })
+            })?;
+            entry.cast_to(&self.type_info, self.mode, &self.conversion)?;
+            casted.push((idx, entry));
+        }
+        for (idx, entry) in casted {
+            if let Some(e) = container.get_by_idx_mut(idx) {
+                *e = entry;
+            }
+        }
         Ok(())
     }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        if self.mode == CastMode::Strict {
+            for idx in self.idx.resolve(schema)? {
+                if let Some(entry) = schema.get_by_idx(idx) {
+                    check_convertible(entry.get_type_info(), &self.type_info).map_err(|e| {
+                        VenumTdsTransRichError::Target {
+                            idx,
+                            name: String::from(entry.get_name()),
+                            cause: Box::new(e),
+                        }
+                    })?;
+                }
+            }
+        }
+        self.apply(schema)
+    }
 }
 
-pub struct SplitItemAtIdx<S: Split> {
-    pub idx: usize,
+/// Adds a copy of the selected item (with its data) as the item `to` (idx, name).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyItem<I = usize> {
+    pub from: I,
+    pub to: (usize, String),
+}
+impl<CONT, ENTRY, I> TransrichItemsProducer<CONT> for CopyItem<I>
+where
+    ENTRY: VDataContainerItem + Clone,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+{
+    fn produce(&self, container: &CONT) -> Result<NewItems<ENTRY>> {
+        let from = self.from.resolve_one(container)?;
+        let mut copy = container.get_by_idx(from).cloned().ok_or_else(|| {
+            VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::Generic {
+                msg: format!("No DataEntry with idx {}. Can't copy.", from),
+            })
+        })?;
+        copy.set_idx(self.to.0);
+        copy.set_name(&self.to.1);
+        Ok(NewItems {
+            items: vec![copy],
+            replaced: Vec::new(),
+        })
+    }
+}
+impl<CONT, ENTRY, I> TransrichContainerInplace<CONT> for CopyItem<I>
+where
+    ENTRY: VDataContainerItem + Clone,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        add_items(container, self.produce(container)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+/// Sorts the items of the container by idx, so the physical order matches the idx order.
+fn sort_items_by_idx<CONT: TransrichContainerItems>(container: &mut CONT) {
+    container.items_mut().sort_by_key(|e| e.get_idx());
+}
+
+/// Moves the item matched by the selector to idx `to`. The items between the old and the new idx
+/// are shifted by one to close the gap and make room, all others keep their idx.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveItem {
+    pub from: ItemSelector,
+    pub to: usize,
+}
+impl<C> TransrichContainerInplace<C> for MoveItem
+where
+    C: TransrichContainerItems,
+{
+    fn apply(&self, data_container: &mut C) -> Result<()> {
+        let from = self.from.resolve_one(data_container)?;
+        if data_container.get_by_idx(from).is_none() {
+            return Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::Generic {
+                    msg: format!("No DataEntry with idx {}. Can't move.", from),
+                },
+            ));
+        }
+        for e in data_container.items_mut() {
+            let idx = e.get_idx();
+            if idx == from {
+                e.set_idx(self.to);
+            } else if from < idx && idx <= self.to {
+                e.set_idx(idx - 1);
+            } else if self.to <= idx && idx < from {
+                e.set_idx(idx + 1);
+            }
+        }
+        sort_items_by_idx(data_container);
+        Ok(())
+    }
+}
+
+/// Swaps the idx of the two items matched by the selectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapItems {
+    pub a: ItemSelector,
+    pub b: ItemSelector,
+}
+impl<C> TransrichContainerInplace<C> for SwapItems
+where
+    C: TransrichContainerItems,
+{
+    fn apply(&self, data_container: &mut C) -> Result<()> {
+        let a = self.a.resolve_one(data_container)?;
+        let b = self.b.resolve_one(data_container)?;
+        for idx in [a, b] {
+            if data_container.get_by_idx(idx).is_none() {
+                return Err(VenumTdsTransRichError::ContainerOps(
+                    ContainerOpsErrors::Generic {
+                        msg: format!("No DataEntry with idx {}. Can't swap.", idx),
+                    },
+                ));
+            }
+        }
+        for e in data_container.items_mut() {
+            if e.get_idx() == a {
+                e.set_idx(b);
+            } else if e.get_idx() == b {
+                e.set_idx(a);
+            }
+        }
+        sort_items_by_idx(data_container);
+        Ok(())
+    }
+}
+
+/// What `ReorderItems` does with the items that are not in its list of names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnlistedItems {
+    /// Fail, before the container is modified.
+    #[default]
+    Fail,
+    /// Put them after the listed items, in their current order.
+    Append,
+    /// Delete them.
+    Delete,
+}
+
+/// Reorders the whole container by an explicit list of item names: the listed items get the idx
+/// 0..n in the order of the list. Unknown or duplicate names are an error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReorderItems {
+    pub names: Vec<String>,
+    pub unlisted: UnlistedItems,
+}
+impl<C> TransrichContainerInplace<C> for ReorderItems
+where
+    C: TransrichContainerItems,
+{
+    fn apply(&self, data_container: &mut C) -> Result<()> {
+        let mut listed = Vec::with_capacity(self.names.len());
+        for name in &self.names {
+            let idx = ItemSelector::Name(name.clone()).resolve_one(data_container)?;
+            if listed.contains(&idx) {
+                return Err(VenumTdsTransRichError::ContainerOps(
+                    ContainerOpsErrors::Generic {
+                        msg: format!("Name {} is listed more than once. Can't reorder.", name),
+                    },
+                ));
+            }
+            listed.push(idx);
+        }
+        let mut unlisted: Vec<usize> = data_container
+            .items()
+            .iter()
+            .map(|e| e.get_idx())
+            .filter(|idx| !listed.contains(idx))
+            .collect();
+        unlisted.sort_unstable();
+
+        match (self.unlisted, unlisted.first()) {
+            (UnlistedItems::Fail, Some(idx)) => {
+                return Err(VenumTdsTransRichError::ContainerOps(
+                    ContainerOpsErrors::Generic {
+                        msg: format!("Item at idx {} is not listed. Can't reorder.", idx),
+                    },
+                ))
+            }
+            (UnlistedItems::Delete, _) => {
+                for idx in unlisted.drain(..) {
+                    data_container.del_by_idx(idx)?;
+                }
+            }
+            _ => {}
+        }
+
+        let order: Vec<usize> = listed.into_iter().chain(unlisted).collect();
+        for e in data_container.items_mut() {
+            if let Some(pos) = order.iter().position(|idx| *idx == e.get_idx()) {
+                e.set_idx(pos);
+            }
+        }
+        sort_items_by_idx(data_container);
+        Ok(())
+    }
+}
+
+/// Renumbers the items to 0..n, keeping their current idx order, e.g. to close the gaps left by
+/// deleted items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactItemIdxs;
+impl<C> TransrichContainerInplace<C> for CompactItemIdxs
+where
+    C: TransrichContainerItems,
+{
+    fn apply(&self, data_container: &mut C) -> Result<()> {
+        sort_items_by_idx(data_container);
+        for (pos, e) in data_container.items_mut().iter_mut().enumerate() {
+            e.set_idx(pos);
+        }
+        Ok(())
+    }
+}
+
+/// Describes a (new) target item: (type_info, idx, name)
+pub type TargetItem = (Value, usize, String);
+
+/// A target of an op: the item to create and the options to convert its value with, if not the
+/// default ones.
+pub trait Target {
+    fn item(&self) -> &TargetItem;
+
+    fn conversion(&self) -> Option<&ConversionOptions> {
+        None
+    }
+}
+
+impl Target for TargetItem {
+    fn item(&self) -> &TargetItem {
+        self
+    }
+}
+
+/// A target item with its own conversion options, e.g. for a decimal comma or a date format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvertedTarget {
+    pub item: TargetItem,
+    pub conversion: Option<ConversionOptions>,
+}
+
+impl Target for ConvertedTarget {
+    fn item(&self) -> &TargetItem {
+        &self.item
+    }
+
+    fn conversion(&self) -> Option<&ConversionOptions> {
+        self.conversion.as_ref()
+    }
+}
+
+/// The conversion options of the targets that have any, for the item level.
+fn target_conversions<'t, T: Target + 't>(
+    targets: impl IntoIterator<Item = &'t T>,
+) -> TargetConversions {
+    TargetConversions(
+        targets
+            .into_iter()
+            .filter_map(|t| t.conversion().map(|c| (t.item().1, c.clone())))
+            .collect(),
+    )
+}
+
+/// What to do, if a new item collides with an existing one, i.e. has the same idx or (non-empty)
+/// name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetConflict {
+    /// Fail, before the container is modified.
+    #[default]
+    Fail,
+    /// Replace the existing item(s).
+    Overwrite,
+    /// Don't add the new item, keep the existing one.
+    Skip,
+    /// Increment the idx of all items at or after the idx of the new item, so every new item ends
+    /// up at its own idx. Name collisions and new items sharing an idx are still an error.
+    Shift,
+}
+
+fn collides<ENTRY: VDataContainerItem>(a: &ENTRY, b: &ENTRY) -> bool {
+    a.get_idx() == b.get_idx() || (!a.get_name().is_empty() && a.get_name() == b.get_name())
+}
+
+fn conflict_err<ENTRY: VDataContainerItem>(item: &ENTRY, msg: String) -> VenumTdsTransRichError {
+    VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::ItemConflict {
+        idx: item.get_idx(),
+        name: String::from(item.get_name()),
+        msg,
+    })
+}
+
+/// Deletes the items at `delete_idxs` (if any) and adds `items` according to `on_conflict`.
+/// Conflicts are checked before anything is modified, so on error the container is untouched.
+fn put_items<CONT, ENTRY>(
+    container: &mut CONT,
+    items: Vec<ENTRY>,
+    on_conflict: TargetConflict,
+    delete_idxs: &[usize],
+) -> Result<()>
+where
+    ENTRY: VDataContainerItem,
+    CONT: TransrichContainerItems<ITEM = ENTRY>,
+{
+    let remaining = || {
+        container
+            .items()
+            .iter()
+            .filter(|e| !delete_idxs.contains(&e.get_idx()))
+    };
+    for (i, item) in items.iter().enumerate() {
+        let existing = remaining().find(|e| collides(*e, item));
+        let previous = items[..i].iter().find(|e| collides(*e, item));
+        match (on_conflict, existing.or(previous)) {
+            (TargetConflict::Fail, Some(other)) => {
+                return Err(conflict_err(
+                    item,
+                    format!(
+                        "collides with item {} at idx {}",
+                        other.get_name(),
+                        other.get_idx()
+                    ),
+                ))
+            }
+            (TargetConflict::Shift, Some(_)) => {
+                let same_name =
+                    |e: &ENTRY| !item.get_name().is_empty() && e.get_name() == item.get_name();
+                if remaining().any(same_name) || items[..i].iter().any(same_name) {
+                    return Err(conflict_err(
+                        item,
+                        String::from("an item with the same name exists. Can't shift."),
+                    ));
+                }
+                if items[..i].iter().any(|e| e.get_idx() == item.get_idx()) {
+                    return Err(conflict_err(
+                        item,
+                        String::from("another new item has the same idx. Can't shift."),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for idx in delete_idxs {
+        if container.get_by_idx(*idx).is_some() {
+            container.del_by_idx(*idx)?;
+        }
+    }
+    let mut items = items;
+    if on_conflict == TargetConflict::Shift {
+        // in ascending order, shifting for a new item never moves the ones added before it
+        items.sort_by_key(|e| e.get_idx());
+    }
+    for item in items {
+        match on_conflict {
+            TargetConflict::Fail => {}
+            TargetConflict::Skip => {
+                if container.items().iter().any(|e| collides(e, &item)) {
+                    continue;
+                }
+            }
+            TargetConflict::Overwrite => {
+                while let Some(idx) = container
+                    .items()
+                    .iter()
+                    .find(|e| collides(*e, &item))
+                    .map(|e| e.get_idx())
+                {
+                    container.del_by_idx(idx)?;
+                }
+            }
+            TargetConflict::Shift => {
+                if container.get_by_idx(item.get_idx()).is_some() {
+                    for e in container.items_mut() {
+                        if e.get_idx() >= item.get_idx() {
+                            e.set_idx(e.get_idx() + 1);
+                        }
+                    }
+                }
+            }
+        }
+        container.add(item);
+    }
+    Ok(())
+}
+
+/// Deletes the replaced items and adds the new ones, as they are.
+fn add_items<CONT: VDataContainer>(
+    container: &mut CONT,
+    new_items: NewItems<CONT::ITEM>,
+) -> Result<()> {
+    for idx in new_items.replaced {
+        if container.get_by_idx(idx).is_some() {
+            container.del_by_idx(idx)?;
+        }
+    }
+    for item in new_items.items {
+        container.add(item);
+    }
+    Ok(())
+}
+
+/// Adds the new items of `op` according to `on_conflict`, i.e. checks them for collisions with the
+/// existing items first. Without it, item-producing ops (`AddItem`, `CopyItem`, the splits and
+/// merges) add their items as they are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnConflict<OP> {
+    pub op: OP,
+    pub on_conflict: TargetConflict,
+}
+impl<CONT, OP> TransrichContainerInplace<CONT> for OnConflict<OP>
+where
+    CONT: TransrichContainerItems,
+    OP: TransrichItemsProducer<CONT>,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        let new_items = self.op.produce(container)?;
+        put_items(
+            container,
+            new_items.items,
+            self.on_conflict,
+            &new_items.replaced,
+        )
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        let new_items = self.op.produce_schema(schema)?;
+        put_items(
+            schema,
+            new_items.items,
+            self.on_conflict,
+            &new_items.replaced,
+        )
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+fn source_idxs_to_delete(delete: bool, idxs: &[usize]) -> Vec<usize> {
+    match delete {
+        true => idxs.to_vec(),
+        false => Vec::new(),
+    }
+}
+
+/// Checks that no two items of the container share an idx or a (non-empty) name.
+pub fn validate_unique_items<CONT>(container: &CONT) -> Result<()>
+where
+    CONT: TransrichContainerItems,
+{
+    let items = container.items();
+    for (i, item) in items.iter().enumerate() {
+        if let Some(other) = items[..i].iter().find(|e| collides(*e, item)) {
+            return Err(conflict_err(
+                item,
+                format!(
+                    "duplicate of item {} at idx {}",
+                    other.get_name(),
+                    other.get_idx()
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn new_target_item<ENTRY, T>(target: &T) -> ENTRY
+where
+    ENTRY: VDataContainerItem + Default,
+    T: Target,
+{
+    let (type_info, idx, name) = target.item();
+    let mut t = ENTRY::default();
+    t.set_type_info(type_info.clone());
+    t.set_idx(*idx);
+    t.set_name(name);
+    t
+}
+
+/// The idx of the item to split in a schema. Fails, if there is no such item or the splitter
+/// can't split items of its type.
+fn schema_split_source<CONT>(
+    schema: &CONT,
+    selector: &impl SelectItems<CONT>,
+    accepts_type: impl Fn(&Value) -> bool,
+) -> Result<usize>
+where
+    CONT: VDataContainer,
+{
+    let idx = selector.resolve_one(schema)?;
+    let entry = schema.get_by_idx(idx).ok_or_else(|| {
+        VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::DivideItemError {
+            idx,
+            msg: format!("Container does not have an entry at idx: {}", idx),
+        })
+    })?;
+    match accepts_type(entry.get_type_info()) {
+        true => Ok(idx),
+        false => Err(VenumTdsTransRichError::ContainerOps(
+            ContainerOpsErrors::DivideItemError {
+                idx,
+                msg: format!(
+                    "Splitter can't split items of type {}",
+                    type_name(entry.get_type_info())
+                ),
+            },
+        )),
+    }
+}
+
+pub struct SplitItemAtIdx<S: Split, I = usize, T = TargetItem> {
+    pub idx: I,
     pub divider: S,
-    pub target_left: (Value, usize, String),
-    pub target_right: (Value, usize, String),
+    pub target_left: T,
+    pub target_right: T,
     pub delete_source_item: bool,
 }
 
-impl<CONT, ENTRY, SPLITIMPL> TransrichContainerInplace<CONT> for SplitItemAtIdx<SPLITIMPL>
+impl<CONT, ENTRY, SPLITIMPL, I, T> TransrichItemsProducer<CONT> for SplitItemAtIdx<SPLITIMPL, I, T>
 where
     SPLITIMPL: Split, // The divider "implementation" to use, to split an ITEM of type Value. This is the lowest level
     ENTRY: VDataContainerItem + SplitUsing<SPLITIMPL, ITEM = ENTRY> + Default, // Entries (of the container) must be container items that also implement "divideUsing", which relies on a certain divide implementation (given above)
     CONT: VDataContainer<ITEM = ENTRY>, // The container where we want to divide an item inside, making use of the 'divideUsing' of the entry and in turn the 'divide' implementation
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn produce(&self, container: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx = self.idx.resolve_one(container)?;
+        let entry = container.get_by_idx(idx).ok_or_else(|| {
+            VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::DivideItemError {
+                idx,
+                msg: format!("Container does not have an entry at idx: {}", idx),
+            })
+        })?;
+
+        let mut t_left = new_target_item::<ENTRY, _>(&self.target_left);
+        let mut t_right = new_target_item::<ENTRY, _>(&self.target_right);
+
+        let conversions = target_conversions([&self.target_left, &self.target_right]);
+        entry.split_using_with(&self.divider, &mut t_left, &mut t_right, &conversions)?;
+
+        Ok(NewItems {
+            items: vec![t_left, t_right],
+            replaced: source_idxs_to_delete(self.delete_source_item, &[idx]),
+        })
+    }
+
+    fn produce_schema(&self, schema: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx = schema_split_source(schema, &self.idx, |t| self.divider.accepts_type(t))?;
+        Ok(NewItems {
+            items: vec![
+                new_target_item(&self.target_left),
+                new_target_item(&self.target_right),
+            ],
+            replaced: source_idxs_to_delete(self.delete_source_item, &[idx]),
+        })
+    }
+}
+
+impl<CONT, ENTRY, SPLITIMPL, I, T> TransrichContainerInplace<CONT>
+    for SplitItemAtIdx<SPLITIMPL, I, T>
+where
+    SPLITIMPL: Split,
+    ENTRY: VDataContainerItem + SplitUsing<SPLITIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
 {
     fn apply(&self, container: &mut CONT) -> Result<()> {
-        let entry = container.get_by_idx_mut(self.idx).ok_or_else(|| {
+        add_items(container, self.produce(container)?)
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        add_items(schema, self.produce_schema(schema)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+/// Splits the item at `idx` into N new items, described by `targets` (see `Target`).
+pub struct SplitItemAtIdxN<S: SplitN, I = usize, T = TargetItem> {
+    pub idx: I,
+    pub divider: S,
+    pub targets: Vec<T>,
+    pub on_mismatch: SplitNTargetMismatch,
+    pub delete_source_item: bool,
+}
+
+impl<CONT, ENTRY, SPLITIMPL, I, T> TransrichItemsProducer<CONT> for SplitItemAtIdxN<SPLITIMPL, I, T>
+where
+    SPLITIMPL: SplitN,
+    ENTRY: VDataContainerItem + SplitNUsing<SPLITIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn produce(&self, container: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx = self.idx.resolve_one(container)?;
+        let entry = container.get_by_idx(idx).ok_or_else(|| {
             VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::DivideItemError {
-                idx: self.idx,
-                msg: format!("Container does not have an entry at idx: {}", self.idx),
+                idx,
+                msg: format!("Container does not have an entry at idx: {}", idx),
             })
         })?;
 
-        let mut t_left = ENTRY::default();
-        t_left.set_type_info(self.target_left.0.clone());
-        t_left.set_idx(self.target_left.1);
-        t_left.set_name(&self.target_left.2);
-
-        let mut t_right = ENTRY::default();
-        t_right.set_type_info(self.target_right.0.clone());
-        t_right.set_idx(self.target_right.1);
-        t_right.set_name(&self.target_right.2);
-
-        let div_res = entry.split_using(&self.divider, &mut t_left, &mut t_right);
-        if div_res.is_ok() {
-            container.add(t_left);
-            container.add(t_right);
-            if self.delete_source_item {
-                container.del_by_idx(self.idx).unwrap();
-            }
+        let mut targets: Vec<ENTRY> = self.targets.iter().map(new_target_item).collect();
+        entry.split_n_using_with(
+            &self.divider,
+            &mut targets,
+            &self.on_mismatch,
+            &target_conversions(&self.targets),
+        )?;
+
+        Ok(NewItems {
+            items: targets,
+            replaced: source_idxs_to_delete(self.delete_source_item, &[idx]),
+        })
+    }
+
+    fn produce_schema(&self, schema: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx = schema_split_source(schema, &self.idx, |t| self.divider.accepts_type(t))?;
+        Ok(NewItems {
+            items: self.targets.iter().map(new_target_item).collect(),
+            replaced: source_idxs_to_delete(self.delete_source_item, &[idx]),
+        })
+    }
+}
+
+impl<CONT, ENTRY, SPLITIMPL, I, T> TransrichContainerInplace<CONT>
+    for SplitItemAtIdxN<SPLITIMPL, I, T>
+where
+    SPLITIMPL: SplitN,
+    ENTRY: VDataContainerItem + SplitNUsing<SPLITIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        add_items(container, self.produce(container)?)
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        add_items(schema, self.produce_schema(schema)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+/// Derives date/time components (year, quarter, ISO week, ...) of the item at `idx` into new items.
+/// `targets` maps each component to a target (type_info, idx, name). See `ValueDateTimeSplitN`.
+pub struct SplitDateTimeItemAtIdx<I = usize> {
+    pub idx: I,
+    pub targets: Vec<(DateTimeComponent, TargetItem)>,
+    pub parse_fmt: Option<String>,
+    pub split_none: bool,
+    pub delete_source_item: bool,
+}
+
+impl<I: Clone> SplitDateTimeItemAtIdx<I> {
+    fn split_n(&self) -> SplitItemAtIdxN<ValueDateTimeSplitN, I> {
+        SplitItemAtIdxN {
+            idx: self.idx.clone(),
+            divider: ValueDateTimeSplitN {
+                components: self.targets.iter().map(|(c, _)| c.clone()).collect(),
+                parse_fmt: self.parse_fmt.clone(),
+                split_none: self.split_none,
+            },
+            targets: self.targets.iter().map(|(_, t)| t.clone()).collect(),
+            on_mismatch: SplitNTargetMismatch::Fail,
+            delete_source_item: self.delete_source_item,
         }
-        div_res
     }
 }
 
-// TODO: MergeItemsAs(pub usize, pub usize); separator|template;
+impl<CONT, ENTRY, I> TransrichItemsProducer<CONT> for SplitDateTimeItemAtIdx<I>
+where
+    ENTRY: VDataContainerItem + SplitNUsing<ValueDateTimeSplitN, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT> + Clone,
+{
+    fn produce(&self, container: &CONT) -> Result<NewItems<ENTRY>> {
+        self.split_n().produce(container)
+    }
 
-#[cfg(test)]
-mod tests {
-    use venum::venum::Value;
-    use venum_tds::{cell::DataCell, row::DataCellRow};
+    fn produce_schema(&self, schema: &CONT) -> Result<NewItems<ENTRY>> {
+        self.split_n().produce_schema(schema)
+    }
+}
+
+impl<CONT, ENTRY, I> TransrichContainerInplace<CONT> for SplitDateTimeItemAtIdx<I>
+where
+    ENTRY: VDataContainerItem + SplitNUsing<ValueDateTimeSplitN, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT> + Clone,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        add_items(container, self.produce(container)?)
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        add_items(schema, self.produce_schema(schema)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+/// Like `SplitItemAtIdxN`, but the tokens are assigned to the targets by token name (e.g. the names
+/// of regex capture groups) instead of by position. `targets` maps a token name to a target.
+/// Tokens without a target are dropped.
+pub struct SplitItemAtIdxNamed<S: SplitNNamed, I = usize, T = TargetItem> {
+    pub idx: I,
+    pub divider: S,
+    pub targets: Vec<(String, T)>,
+    pub delete_source_item: bool,
+}
+
+impl<CONT, ENTRY, SPLITIMPL, I, T> TransrichItemsProducer<CONT>
+    for SplitItemAtIdxNamed<SPLITIMPL, I, T>
+where
+    SPLITIMPL: SplitNNamed,
+    ENTRY: VDataContainerItem + SplitNUsing<SPLITIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn produce(&self, container: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx = self.idx.resolve_one(container)?;
+        let target_descrs = self.target_per_token(idx)?;
+
+        let entry = container.get_by_idx(idx).ok_or_else(|| {
+            VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::DivideItemError {
+                idx,
+                msg: format!("Container does not have an entry at idx: {}", idx),
+            })
+        })?;
+
+        // unmapped tokens go into throwaway String targets
+        let throwaway = (Value::string_default(), idx, String::new());
+        let mut targets: Vec<ENTRY> = target_descrs
+            .iter()
+            .map(|t| new_target_item(t.map_or(&throwaway, Target::item)))
+            .collect();
+        entry.split_n_using_with(
+            &self.divider,
+            &mut targets,
+            &SplitNTargetMismatch::Fail,
+            &target_conversions(self.targets.iter().map(|(_, t)| t)),
+        )?;
+
+        Ok(NewItems {
+            items: targets
+                .into_iter()
+                .zip(target_descrs)
+                .filter_map(|(t, descr)| descr.map(|_| t))
+                .collect(),
+            replaced: source_idxs_to_delete(self.delete_source_item, &[idx]),
+        })
+    }
+
+    fn produce_schema(&self, schema: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx = schema_split_source(schema, &self.idx, |t| self.divider.accepts_type(t))?;
+        Ok(NewItems {
+            items: self
+                .target_per_token(idx)?
+                .into_iter()
+                .flatten()
+                .map(new_target_item)
+                .collect(),
+            replaced: source_idxs_to_delete(self.delete_source_item, &[idx]),
+        })
+    }
+}
+
+impl<CONT, ENTRY, SPLITIMPL, I, T> TransrichContainerInplace<CONT>
+    for SplitItemAtIdxNamed<SPLITIMPL, I, T>
+where
+    SPLITIMPL: SplitNNamed,
+    ENTRY: VDataContainerItem + SplitNUsing<SPLITIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        add_items(container, self.produce(container)?)
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        add_items(schema, self.produce_schema(schema)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+impl<S: SplitNNamed, I, T> SplitItemAtIdxNamed<S, I, T> {
+    /// One (positional) target per token, `None` for tokens without a target. Fails, if a target
+    /// refers to a token the splitter doesn't have.
+    fn target_per_token(&self, idx: usize) -> Result<Vec<Option<&T>>> {
+        let token_names = self.divider.token_names();
+        if let Some((unknown, _)) = self
+            .targets
+            .iter()
+            .find(|(name, _)| !token_names.iter().flatten().any(|tn| tn == name))
+        {
+            return Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::DivideItemError {
+                    idx,
+                    msg: format!("Splitter has no token named: {}", unknown),
+                },
+            ));
+        }
+        Ok(token_names
+            .iter()
+            .map(|tn| {
+                tn.as_ref().and_then(|tn| {
+                    self.targets
+                        .iter()
+                        .find(|(name, _)| name == tn)
+                        .map(|(_, target)| target)
+                })
+            })
+            .collect())
+    }
+}
+
+/// Splits the item at `idx` into (key, value) pairs and puts every value into the target configured
+/// for its key. `targets` are (key, default, target) triples, where the default is used, if the
+/// key is missing.
+pub struct SplitItemAtIdxByKey<S: SplitKeyed, I = usize, T = TargetItem> {
+    pub idx: I,
+    pub divider: S,
+    pub targets: Vec<(String, Option<Value>, T)>,
+    pub unknown_keys: UnknownKeys<T>,
+    pub delete_source_item: bool,
+}
+
+impl<CONT, ENTRY, SPLITIMPL, I, T> TransrichItemsProducer<CONT>
+    for SplitItemAtIdxByKey<SPLITIMPL, I, T>
+where
+    SPLITIMPL: SplitKeyed,
+    ENTRY: VDataContainerItem + SplitKeyedUsing<SPLITIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn produce(&self, container: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx = self.idx.resolve_one(container)?;
+        let entry = container.get_by_idx(idx).ok_or_else(|| {
+            VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::DivideItemError {
+                idx,
+                msg: format!("Container does not have an entry at idx: {}", idx),
+            })
+        })?;
+
+        let mut targets: Vec<(String, Option<Value>, ENTRY)> = self
+            .targets
+            .iter()
+            .map(|(key, default, t)| (key.clone(), default.clone(), new_target_item(t)))
+            .collect();
+        let mut remainder: UnknownKeys<ENTRY> = self.unknown_keys.as_ref().map(new_target_item);
+        entry.split_keyed_using_with(
+            &self.divider,
+            &mut targets,
+            remainder.as_mut(),
+            &target_conversions(
+                self.targets
+                    .iter()
+                    .map(|(_, _, t)| t)
+                    .chain(self.unknown_keys.as_ref().collect_into()),
+            ),
+        )?;
+
+        Ok(NewItems {
+            items: targets
+                .into_iter()
+                .map(|(_, _, t)| t)
+                .chain(remainder.collect_into())
+                .collect(),
+            replaced: source_idxs_to_delete(self.delete_source_item, &[idx]),
+        })
+    }
+
+    fn produce_schema(&self, schema: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx = schema_split_source(schema, &self.idx, |t| self.divider.accepts_type(t))?;
+        Ok(NewItems {
+            items: self
+                .targets
+                .iter()
+                .map(|(_, _, t)| t)
+                .chain(self.unknown_keys.as_ref().collect_into())
+                .map(new_target_item)
+                .collect(),
+            replaced: source_idxs_to_delete(self.delete_source_item, &[idx]),
+        })
+    }
+}
+
+impl<CONT, ENTRY, SPLITIMPL, I, T> TransrichContainerInplace<CONT>
+    for SplitItemAtIdxByKey<SPLITIMPL, I, T>
+where
+    SPLITIMPL: SplitKeyed,
+    ENTRY: VDataContainerItem + SplitKeyedUsing<SPLITIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        add_items(container, self.produce(container)?)
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        add_items(schema, self.produce_schema(schema)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+fn merge_source_missing_err(idx: usize) -> VenumTdsTransRichError {
+    VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::MergeItemsError {
+        idx,
+        msg: format!("Container does not have an entry at idx: {}", idx),
+    })
+}
+
+/// Merges the items at `idx_a` and `idx_b` into a new item, described by `target` (see `Target`).
+/// The sources are deleted before the target is added, so the target may re-use one of the source idx.
+pub struct MergeItemsAs<M: Merge, I = usize, T = TargetItem> {
+    pub idx_a: I,
+    pub idx_b: I,
+    pub merger: M,
+    pub target: T,
+    pub delete_source_items: bool,
+}
+
+impl<CONT, ENTRY, MERGEIMPL, I, T> TransrichItemsProducer<CONT> for MergeItemsAs<MERGEIMPL, I, T>
+where
+    MERGEIMPL: Merge,
+    ENTRY: VDataContainerItem + MergeUsing<MERGEIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn produce(&self, container: &CONT) -> Result<NewItems<ENTRY>> {
+        let idx_a = self.idx_a.resolve_one(container)?;
+        let idx_b = self.idx_b.resolve_one(container)?;
+        let src_a = container
+            .get_by_idx(idx_a)
+            .ok_or_else(|| merge_source_missing_err(idx_a))?;
+        let src_b = container
+            .get_by_idx(idx_b)
+            .ok_or_else(|| merge_source_missing_err(idx_b))?;
+
+        let mut t = new_target_item::<ENTRY, _>(&self.target);
+        let conversions = target_conversions([&self.target]);
+        src_a.merge_using_with(&self.merger, src_b, &mut t, &conversions)?;
+
+        Ok(NewItems {
+            items: vec![t],
+            replaced: source_idxs_to_delete(self.delete_source_items, &[idx_a, idx_b]),
+        })
+    }
+
+    fn produce_schema(&self, schema: &CONT) -> Result<NewItems<ENTRY>> {
+        let idxs = [
+            self.idx_a.resolve_one(schema)?,
+            self.idx_b.resolve_one(schema)?,
+        ];
+        merge_schema(schema, &idxs, &self.target, self.delete_source_items)
+    }
+}
+
+impl<CONT, ENTRY, MERGEIMPL, I, T> TransrichContainerInplace<CONT> for MergeItemsAs<MERGEIMPL, I, T>
+where
+    MERGEIMPL: Merge,
+    ENTRY: VDataContainerItem + MergeUsing<MERGEIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        add_items(container, self.produce(container)?)
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        add_items(schema, self.produce_schema(schema)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+/// Like `MergeItemsAs`, but merges an arbitrary number of items, in the order given by `idxs`.
+pub struct MergeNItemsAs<M: MergeN, I = usize, T = TargetItem> {
+    pub idxs: Vec<I>,
+    pub merger: M,
+    pub target: T,
+    pub delete_source_items: bool,
+}
+
+impl<CONT, ENTRY, MERGEIMPL, I, T> TransrichItemsProducer<CONT> for MergeNItemsAs<MERGEIMPL, I, T>
+where
+    MERGEIMPL: MergeN,
+    ENTRY: VDataContainerItem + MergeNUsing<MERGEIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn produce(&self, container: &CONT) -> Result<NewItems<ENTRY>> {
+        let mut idxs: Vec<usize> = Vec::with_capacity(self.idxs.len());
+        for selector in &self.idxs {
+            idxs.extend(selector.resolve(container)?);
+        }
+        let (first_idx, other_idxs) = idxs.split_first().ok_or_else(|| {
+            VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::Generic {
+                msg: String::from("No source idx given. Nothing to merge."),
+            })
+        })?;
+        let first = container
+            .get_by_idx(*first_idx)
+            .ok_or_else(|| merge_source_missing_err(*first_idx))?;
+        let others = other_idxs
+            .iter()
+            .map(|idx| {
+                container
+                    .get_by_idx(*idx)
+                    .ok_or_else(|| merge_source_missing_err(*idx))
+            })
+            .collect::<Result<Vec<&ENTRY>>>()?;
+
+        let mut t = new_target_item::<ENTRY, _>(&self.target);
+        let conversions = target_conversions([&self.target]);
+        first.merge_n_using_with(&self.merger, others, &mut t, &conversions)?;
+
+        Ok(NewItems {
+            items: vec![t],
+            replaced: source_idxs_to_delete(self.delete_source_items, &idxs),
+        })
+    }
+
+    fn produce_schema(&self, schema: &CONT) -> Result<NewItems<ENTRY>> {
+        let mut idxs: Vec<usize> = Vec::with_capacity(self.idxs.len());
+        for selector in &self.idxs {
+            idxs.extend(selector.resolve(schema)?);
+        }
+        if idxs.is_empty() {
+            return Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::Generic {
+                    msg: String::from("No source idx given. Nothing to merge."),
+                },
+            ));
+        }
+        merge_schema(schema, &idxs, &self.target, self.delete_source_items)
+    }
+}
+
+impl<CONT, ENTRY, MERGEIMPL, I, T> TransrichContainerInplace<CONT>
+    for MergeNItemsAs<MERGEIMPL, I, T>
+where
+    MERGEIMPL: MergeN,
+    ENTRY: VDataContainerItem + MergeNUsing<MERGEIMPL, ITEM = ENTRY> + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+    I: SelectItems<CONT>,
+    T: Target,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        add_items(container, self.produce(container)?)
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        add_items(schema, self.produce_schema(schema)?)
+    }
+
+    fn has_targets(&self) -> bool {
+        true
+    }
+}
+
+/// The new items of merging the items at `idxs` of a schema: all sources must exist, their types
+/// aren't checked, as mergers stringify their sources.
+fn merge_schema<CONT, ENTRY>(
+    schema: &CONT,
+    idxs: &[usize],
+    target: &impl Target,
+    delete_source_items: bool,
+) -> Result<NewItems<ENTRY>>
+where
+    ENTRY: VDataContainerItem + Default,
+    CONT: VDataContainer<ITEM = ENTRY>,
+{
+    if let Some(idx) = idxs.iter().find(|idx| schema.get_by_idx(**idx).is_none()) {
+        return Err(merge_source_missing_err(*idx));
+    }
+    Ok(NewItems {
+        items: vec![new_target_item(target)],
+        replaced: source_idxs_to_delete(delete_source_items, idxs),
+    })
+}
+
+/// An item matched by `ForEachItem`, handed to the operation factory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchedItem {
+    pub idx: usize,
+    pub name: String,
+    /// The first idx after the highest idx in the container, i.e. where new items can go.
+    pub next_free_idx: usize,
+}
+
+impl MatchedItem {
+    /// A target at `next_free_idx + offset`. `{name}` and `{idx}` in `name_pattern` are replaced
+    /// with the name and idx of the matched item, e.g. `{name}_from`.
+    pub fn target(&self, type_info: Value, name_pattern: &str, offset: usize) -> TargetItem {
+        (
+            type_info,
+            self.next_free_idx + offset,
+            self.name_from(name_pattern),
+        )
+    }
+
+    /// `name_pattern` with `{name}` and `{idx}` replaced with the name and idx of the matched item.
+    pub fn name_from(&self, name_pattern: &str) -> String {
+        name_pattern
+            .replace("{name}", &self.name)
+            .replace("{idx}", &self.idx.to_string())
+    }
+}
+
+/// Applies the operation created by `op_for` to every item matched by `selector`, in ascending
+/// order of idx. The selector is resolved once, before any operation is applied. A failing
+/// operation doesn't stop the others; all errors are reported together, per item. The container is
+/// only modified, if the operations of all items succeed.
+pub struct ForEachItem<F> {
+    pub selector: ItemSelector,
+    pub op_for: F,
+}
+
+impl<CONT, F, OP> TransrichContainerInplace<CONT> for ForEachItem<F>
+where
+    F: Fn(&MatchedItem) -> OP,
+    OP: TransrichContainerInplace<CONT>,
+    CONT: TransrichContainerItems + Clone,
+{
+    fn apply(&self, container: &mut CONT) -> Result<()> {
+        self.run(container, false)
+    }
+
+    fn apply_schema(&self, schema: &mut CONT) -> Result<()> {
+        self.run(schema, true)
+    }
+
+    /// Asks the op created for a placeholder item, the ops of all items are of the same kind.
+    fn has_targets(&self) -> bool {
+        (self.op_for)(&MatchedItem {
+            idx: 0,
+            name: String::new(),
+            next_free_idx: 0,
+        })
+        .has_targets()
+    }
+}
+
+impl<F> ForEachItem<F> {
+    fn run<CONT, OP>(&self, container: &mut CONT, schema: bool) -> Result<()>
+    where
+        F: Fn(&MatchedItem) -> OP,
+        OP: TransrichContainerInplace<CONT>,
+        CONT: TransrichContainerItems + Clone,
+    {
+        let matched: Vec<(usize, String)> = self
+            .selector
+            .resolve(container)?
+            .into_iter()
+            .filter_map(|idx| {
+                container
+                    .get_by_idx(idx)
+                    .map(|item| (idx, String::from(item.get_name())))
+            })
+            .collect();
+
+        let mut result = container.clone();
+        let mut errors = Vec::new();
+        for (idx, name) in matched {
+            let next_free_idx = result
+                .items()
+                .iter()
+                .map(|item| item.get_idx() + 1)
+                .max()
+                .unwrap_or(0);
+            let op = (self.op_for)(&MatchedItem {
+                idx,
+                name: name.clone(),
+                next_free_idx,
+            });
+            let res = match schema {
+                true => op.apply_schema(&mut result),
+                false => op.apply(&mut result),
+            };
+            if let Err(e) = res {
+                errors.push((idx, name, e));
+            }
+        }
+
+        match errors.is_empty() {
+            true => {
+                *container = result;
+                Ok(())
+            }
+            false => Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::ItemErrors { errors },
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use venum::venum::Value;
+    use venum_tds::{cell::DataCell, row::DataCellRow, traits::VDataContainer};
+
+    use crate::{
+        test_util::test_row,
+        value_conversion::NumberLocale,
+        value_merging::{MergeNone, ValueStringJoinMerge, ValueTemplateMerge},
+        value_splitting::{
+            DuplicateKeys, EmptyPolicy, FixedWidthShortRecord, FixedWidthUnit, TokenPolicy,
+            ValueStringFixedWidthSplitN, ValueStringJsonSplitN, ValueStringKeyValueSplit,
+            ValueStringRegexPairSplit, ValueStringRegexSplitN, ValueStringSeparatorCharSplit,
+            ValueStringSeparatorCharSplitN, WithTokenPolicy,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_mutate_idx_of_tds_data_cell() {
+        let m = MutateItemIdx::new(0, 1);
+
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::bool_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        m.apply(&mut c).unwrap();
+        assert_eq!(1, c.0.first().unwrap().idx);
+    }
+
+    #[test]
+    fn test_delete_from_container() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::bool_default(),
+            String::from("col1"),
+            0,
+        ));
+        c.0.push(DataCell::new_without_data(
+            Value::bool_default(),
+            String::from("col2"),
+            1,
+        ));
+
+        let container_transricher = DeleteItemAtIdx(0);
+        container_transricher.apply(&mut c).unwrap();
+
+        let container_transricher2 = DeleteItemAtIdx(1);
+        container_transricher2.apply(&mut c).unwrap();
+
+        assert_eq!(0, c.0.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrapped(VenumTdsError(DataAccess(IllegalIdxAccess { idx: 0 })))")]
+    fn test_delete_from_container_err() {
+        let mut c = DataCellRow::new();
+        let container_transricher = DeleteItemAtIdx(0);
+        container_transricher.apply(&mut c).unwrap();
+    }
+
+    #[test]
+    fn test_add_to_container() {
+        let mut c = DataCellRow::new();
+        let container_transricher = AddItem(DataCell::new_without_data(
+            Value::bool_default(),
+            String::from("col1"),
+            0,
+        ));
+        container_transricher.apply(&mut c).unwrap();
+
+        assert_eq!(1, c.0.len());
+    }
+
+    #[test]
+    fn test_combined() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::bool_default(),
+            String::from("col1"),
+            0,
+        ));
+        c.0.push(DataCell::new_without_data(
+            Value::bool_default(),
+            String::from("col2"),
+            1,
+        ));
+
+        let mut transrichers: Vec<Box<dyn TransrichContainerInplace<DataCellRow>>> =
+            Vec::with_capacity(3);
+
+        transrichers.push(Box::new(AddItem(DataCell::new_without_data(
+            Value::bool_default(),
+            String::from("col3"),
+            2,
+        ))));
+        transrichers.push(Box::new(DeleteItemAtIdx(1)));
+        transrichers.push(Box::new(DeleteItemAtIdx(2)));
+        transrichers.push(Box::new(MutateItemIdx::new(0, 10)));
+
+        transrichers
+            .iter_mut()
+            .map(|t| t.apply(&mut c))
+            .collect::<Result<Vec<()>>>()
+            .unwrap();
+
+        assert_eq!(1, c.0.len());
+        assert_eq!(10, c.0.first().unwrap().get_idx());
+        assert_eq!(String::from("col1"), c.0.first().unwrap().get_name());
+    }
+
+    #[test]
+    pub fn test_split_container_item_using_value_string_separator_char_divider() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("foo:bar"))),
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplit {
+                sep_char: ':',
+                split_none: false,
+            },
+            target_left: (Value::string_default(), 1, String::from("col2")),
+            target_right: (Value::string_default(), 2, String::from("Col3")),
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(3, c.0.len());
+        assert_eq!(
+            &Value::String(String::from("foo")),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::String(String::from("bar")),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_split_container_item_using_value_string_separator_char_divider_delete_src() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("foo:bar"))),
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplit {
+                sep_char: ':',
+                split_none: false,
+            },
+            target_left: (Value::string_default(), 1, String::from("col2")),
+            target_right: (Value::string_default(), 2, String::from("Col3")),
+            delete_source_item: true,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(2, c.0.len());
+        assert_eq!(
+            &Value::String(String::from("foo")),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::String(String::from("bar")),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_split_container_item_using_value_string_separator_char_divider_none() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplit {
+                sep_char: ':',
+                split_none: true,
+            },
+            target_left: (Value::string_default(), 1, String::from("col2")),
+            target_right: (Value::string_default(), 2, String::from("Col3")),
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(3, c.0.len());
+        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
+        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+    }
+
+    #[test]
+    pub fn test_split_container_item_using_value_string_separator_char_divider_none_delete_src() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplit {
+                sep_char: ':',
+                split_none: true,
+            },
+            target_left: (Value::string_default(), 1, String::from("col2")),
+            target_right: (Value::string_default(), 2, String::from("Col3")),
+            delete_source_item: true,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(2, c.0.len());
+        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
+        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"Value is None, but split_none is false\", src_val: None, details: None })"
+    )]
+    pub fn test_split_container_item_using_value_string_separator_char_divider_none_but_split_none_is_false(
+    ) {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplit {
+                sep_char: ':',
+                split_none: false, // <--- !!!
+            },
+            target_left: (Value::string_default(), 1, String::from("col2")),
+            target_right: (Value::string_default(), 2, String::from("Col3")),
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+    }
+
+    #[test]
+    pub fn test_split_container_item_using_value_string_regex_pair_divider() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("1.12 2.23"))),
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringRegexPairSplit::from(
+                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
+                true,
+            )
+            .unwrap(),
+            target_left: (Value::float32_default(), 1, String::from("col2")),
+            target_right: (Value::float32_default(), 2, String::from("Col3")),
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(3, c.0.len());
+        assert_eq!(
+            &Value::Float32(1.12_f32),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Float32(2.23_f32),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_split_container_item_using_value_string_regex_pair_divider_delete_src() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("1.12 2.23"))),
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringRegexPairSplit::from(
+                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
+                true,
+            )
+            .unwrap(),
+            target_left: (Value::float32_default(), 1, String::from("col2")),
+            target_right: (Value::float32_default(), 2, String::from("Col3")),
+            delete_source_item: true,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(2, c.0.len());
+        assert_eq!(
+            &Value::Float32(1.12_f32),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Float32(2.23_f32),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_split_container_item_using_value_string_regex_pair_divider_none() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringRegexPairSplit::from(
+                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
+                true,
+            )
+            .unwrap(),
+            target_left: (Value::float32_default(), 1, String::from("col2")),
+            target_right: (Value::float32_default(), 2, String::from("Col3")),
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(3, c.0.len());
+        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
+        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+    }
+
+    #[test]
+    pub fn test_split_container_item_using_value_string_regex_pair_divider_none_delete_src() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringRegexPairSplit::from(
+                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
+                true,
+            )
+            .unwrap(),
+            target_left: (Value::float32_default(), 1, String::from("col2")),
+            target_right: (Value::float32_default(), 2, String::from("Col3")),
+            delete_source_item: true,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(2, c.0.len());
+        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
+        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"Value is None, but split_none is false\", src_val: None, details: None })"
+    )]
+    pub fn test_split_container_item_using_value_string_regex_pair_divider_none_err() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: ValueStringRegexPairSplit::from(
+                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
+                false,
+            )
+            .unwrap(),
+            target_left: (Value::float32_default(), 1, String::from("col2")),
+            target_right: (Value::float32_default(), 2, String::from("Col3")),
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+    }
+
+    #[test]
+    pub fn test_merge_container_items_using_value_string_join_merge() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("first_name"),
+            0,
+            Some(Value::String(String::from("John"))),
+        ));
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("last_name"),
+            1,
+            Some(Value::String(String::from("Smith"))),
+        ));
+
+        let merge_as = MergeItemsAs {
+            idx_a: 0,
+            idx_b: 1,
+            merger: ValueStringJoinMerge {
+                separator: String::from(" "),
+                merge_none: MergeNone::Skip,
+            },
+            target: (Value::string_default(), 2, String::from("name")),
+            delete_source_items: false,
+        };
+
+        merge_as.apply(&mut c).unwrap();
+
+        assert_eq!(3, c.0.len());
+        assert_eq!(
+            &Value::String(String::from("John Smith")),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_merge_container_items_using_value_template_merge_delete_src() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("int_part"),
+            0,
+            Some(Value::String(String::from("1"))),
+        ));
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("frac_part"),
+            1,
+            Some(Value::String(String::from("12"))),
+        ));
+
+        let merge_as = MergeItemsAs {
+            idx_a: 0,
+            idx_b: 1,
+            merger: ValueTemplateMerge::from(String::from("{0}.{1}"), MergeNone::Fail).unwrap(),
+            target: (Value::float32_default(), 0, String::from("f32_val")),
+            delete_source_items: true,
+        };
+
+        merge_as.apply(&mut c).unwrap();
+
+        assert_eq!(1, c.0.len());
+        assert_eq!(
+            &Value::Float32(1.12_f32),
+            c.get_by_idx(0).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ContainerOps(MergeItemsError { idx: 1, msg: \"Container does not have an entry at idx: 1\" })"
+    )]
+    pub fn test_merge_container_items_err_missing_src() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        let merge_as = MergeItemsAs {
+            idx_a: 0,
+            idx_b: 1,
+            merger: ValueStringJoinMerge {
+                separator: String::from(" "),
+                merge_none: MergeNone::Skip,
+            },
+            target: (Value::string_default(), 2, String::from("col3")),
+            delete_source_items: false,
+        };
+
+        merge_as.apply(&mut c).unwrap();
+    }
+
+    #[test]
+    pub fn test_merge_n_container_items_using_value_string_join_merge_delete_src() {
+        let mut c = DataCellRow::new();
+        for (idx, v) in ["a", "b", "c"].iter().enumerate() {
+            c.0.push(DataCell::new(
+                Value::string_default(),
+                format!("col{}", idx + 1),
+                idx,
+                Some(Value::String(String::from(*v))),
+            ));
+        }
+
+        let merge_as = MergeNItemsAs {
+            idxs: vec![2, 0, 1],
+            merger: ValueStringJoinMerge {
+                separator: String::from("|"),
+                merge_none: MergeNone::Skip,
+            },
+            target: (Value::string_default(), 0, String::from("key")),
+            delete_source_items: true,
+        };
+
+        merge_as.apply(&mut c).unwrap();
+
+        assert_eq!(1, c.0.len());
+        assert_eq!(
+            &Value::String(String::from("c|a|b")),
+            c.get_by_idx(0).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_split_n_container_item_using_value_string_separator_char_split_n() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("foo;1.12;true"))),
+        ));
+
+        let div_at = SplitItemAtIdxN {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplitN {
+                sep_char: ';',
+                split_none: false,
+                split_none_into_num_clones: None,
+            },
+            targets: vec![
+                (Value::string_default(), 1, String::from("col2")),
+                (Value::float32_default(), 2, String::from("col3")),
+                (Value::bool_default(), 3, String::from("col4")),
+            ],
+            on_mismatch: SplitNTargetMismatch::Fail,
+            delete_source_item: true,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(3, c.0.len());
+        assert!(c.get_by_idx(0).is_none());
+        assert_eq!(
+            &Value::String(String::from("foo")),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Float32(1.12_f32),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Bool(true),
+            c.get_by_idx(3).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_split_n_container_item_pad_with_none() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("a;b"))),
+        ));
+
+        let div_at = SplitItemAtIdxN {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplitN {
+                sep_char: ';',
+                split_none: false,
+                split_none_into_num_clones: None,
+            },
+            targets: vec![
+                (Value::string_default(), 1, String::from("col2")),
+                (Value::string_default(), 2, String::from("col3")),
+                (Value::string_default(), 3, String::from("col4")),
+            ],
+            on_mismatch: SplitNTargetMismatch::PadWithNone,
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(4, c.0.len());
+        assert_eq!(
+            &Value::String(String::from("b")),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+        assert_eq!(None, c.get_by_idx(3).unwrap().get_data());
+    }
+
+    #[test]
+    pub fn test_split_n_container_item_fold_tail_into_last() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("a;b;c;d"))),
+        ));
+
+        let div_at = SplitItemAtIdxN {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplitN {
+                sep_char: ';',
+                split_none: false,
+                split_none_into_num_clones: None,
+            },
+            targets: vec![
+                (Value::string_default(), 1, String::from("col2")),
+                (Value::string_default(), 2, String::from("col3")),
+            ],
+            on_mismatch: SplitNTargetMismatch::FoldTailIntoLast(String::from(";")),
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(3, c.0.len());
+        assert_eq!(
+            &Value::String(String::from("a")),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::String(String::from("b;c;d")),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"expected 2 tokens as result of split, but got: 3\""
+    )]
+    pub fn test_split_n_container_item_mismatch_err() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("a;b;c"))),
+        ));
+
+        let div_at = SplitItemAtIdxN {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplitN {
+                sep_char: ';',
+                split_none: false,
+                split_none_into_num_clones: None,
+            },
+            targets: vec![
+                (Value::string_default(), 1, String::from("col2")),
+                (Value::string_default(), 2, String::from("col3")),
+            ],
+            on_mismatch: SplitNTargetMismatch::PadWithNone,
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+    }
+
+    #[test]
+    pub fn test_split_named_container_item_using_value_string_regex_split_n() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("2022-10"))),
+        ));
+
+        let div_at = SplitItemAtIdxNamed {
+            idx: 0,
+            divider: ValueStringRegexSplitN::from(
+                String::from("(?P<year>\\d{4})-(?P<month>\\d{2})(-(?P<day>\\d{2}))?"),
+                true,
+            )
+            .unwrap(),
+            targets: vec![
+                (
+                    String::from("month"),
+                    (Value::uint8_default(), 1, String::from("the_month")),
+                ),
+                (
+                    String::from("day"),
+                    (Value::uint8_default(), 2, String::from("the_day")),
+                ),
+            ],
+            delete_source_item: true,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(2, c.0.len());
+        assert_eq!(
+            &Value::UInt8(10),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ContainerOps(DivideItemError { idx: 0, msg: \"Splitter has no token named: hour\" })"
+    )]
+    pub fn test_split_named_container_item_err_unknown_name() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+        ));
+
+        let div_at = SplitItemAtIdxNamed {
+            idx: 0,
+            divider: ValueStringRegexSplitN::from(String::from("(?P<year>\\d{4})"), true).unwrap(),
+            targets: vec![(
+                String::from("hour"),
+                (Value::uint8_default(), 1, String::from("col2")),
+            )],
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+    }
+
+    #[test]
+    pub fn test_split_n_container_item_using_value_string_fixed_width_split_n() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("record"),
+            0,
+            Some(Value::String(String::from("A12  0042 1.5"))),
+        ));
+
+        let div_at = SplitItemAtIdxN {
+            idx: 0,
+            divider: ValueStringFixedWidthSplitN {
+                ranges: ValueStringFixedWidthSplitN::ranges_from_widths(&[3, 6, 4]),
+                unit: FixedWidthUnit::Chars,
+                trim: true,
+                blank_as_none: true,
+                short_record: FixedWidthShortRecord::Fail,
+                split_none: false,
+            },
+            targets: vec![
+                (Value::string_default(), 1, String::from("code")),
+                (Value::int32_default(), 2, String::from("qty")),
+                (Value::float32_default(), 3, String::from("weight")),
+            ],
+            on_mismatch: SplitNTargetMismatch::Fail,
+            delete_source_item: true,
+        };
 
-    use crate::value_splitting::{ValueStringRegexPairSplit, ValueStringSeparatorCharSplit};
+        div_at.apply(&mut c).unwrap();
 
-    use super::*;
+        assert_eq!(3, c.0.len());
+        assert_eq!(
+            &Value::String(String::from("A12")),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Int32(42),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Float32(1.5_f32),
+            c.get_by_idx(3).unwrap().get_data().unwrap()
+        );
+    }
 
     #[test]
-    fn test_mutate_idx_of_tds_data_cell() {
-        let m = MutateItemIdx::new(0, 1);
-
+    pub fn test_split_by_key_container_item_using_value_string_key_value_split() {
         let mut c = DataCellRow::new();
-        c.0.push(DataCell::new_without_data(
-            Value::bool_default(),
-            String::from("col1"),
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("attrs"),
             0,
+            Some(Value::String(String::from("color=red;qty=3;foo=bar;x=y"))),
         ));
 
-        m.apply(&mut c).unwrap();
-        assert_eq!(1, c.0.first().unwrap().idx);
+        let div_at = SplitItemAtIdxByKey {
+            idx: 0,
+            divider: ValueStringKeyValueSplit {
+                pair_sep: ';',
+                kv_sep: '=',
+                quoting: None,
+                duplicate_keys: DuplicateKeys::Fail,
+                split_none: false,
+            },
+            targets: vec![
+                (
+                    String::from("qty"),
+                    None,
+                    (Value::int32_default(), 1, String::from("qty")),
+                ),
+                (
+                    String::from("size"),
+                    Some(Value::String(String::from("M"))),
+                    (Value::string_default(), 2, String::from("size")),
+                ),
+                (
+                    String::from("color"),
+                    None,
+                    (Value::string_default(), 3, String::from("color")),
+                ),
+            ],
+            unknown_keys: UnknownKeys::CollectInto((
+                Value::string_default(),
+                4,
+                String::from("rest"),
+            )),
+            delete_source_item: true,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(4, c.0.len());
+        assert_eq!(
+            &Value::Int32(3),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::String(String::from("M")),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::String(String::from("red")),
+            c.get_by_idx(3).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::String(String::from("foo=bar;x=y")),
+            c.get_by_idx(4).unwrap().get_data().unwrap()
+        );
     }
 
     #[test]
-    fn test_delete_from_container() {
+    #[should_panic(expected = "Split(SplitError { msg: \"unknown key(s): [\\\"foo\\\"]\"")]
+    pub fn test_split_by_key_container_item_err_unknown_key() {
         let mut c = DataCellRow::new();
-        c.0.push(DataCell::new_without_data(
-            Value::bool_default(),
-            String::from("col1"),
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("attrs"),
             0,
+            Some(Value::String(String::from("color=red;foo=bar"))),
         ));
-        c.0.push(DataCell::new_without_data(
-            Value::bool_default(),
-            String::from("col2"),
-            1,
-        ));
-
-        let container_transricher = DeleteItemAtIdx(0);
-        container_transricher.apply(&mut c).unwrap();
 
-        let container_transricher2 = DeleteItemAtIdx(1);
-        container_transricher2.apply(&mut c).unwrap();
+        let div_at = SplitItemAtIdxByKey {
+            idx: 0,
+            divider: ValueStringKeyValueSplit {
+                pair_sep: ';',
+                kv_sep: '=',
+                quoting: None,
+                duplicate_keys: DuplicateKeys::Fail,
+                split_none: false,
+            },
+            targets: vec![(
+                String::from("color"),
+                None,
+                (Value::string_default(), 1, String::from("color")),
+            )],
+            unknown_keys: UnknownKeys::Fail,
+            delete_source_item: false,
+        };
 
-        assert_eq!(0, c.0.len());
+        div_at.apply(&mut c).unwrap();
     }
 
     #[test]
-    #[should_panic(expected = "Wrapped(VenumTdsError(DataAccess(IllegalIdxAccess { idx: 0 })))")]
-    fn test_delete_from_container_err() {
+    pub fn test_split_named_container_item_using_value_string_json_split_n() {
         let mut c = DataCellRow::new();
-        let container_transricher = DeleteItemAtIdx(0);
-        container_transricher.apply(&mut c).unwrap();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("payload"),
+            0,
+            Some(Value::String(String::from(
+                r#"{"user": {"id": 7, "name": "jane"}}"#,
+            ))),
+        ));
+
+        let div_at = SplitItemAtIdxNamed {
+            idx: 0,
+            divider: ValueStringJsonSplitN::from(
+                vec![String::from("user.id"), String::from("user.name")],
+                false,
+                false,
+            )
+            .unwrap(),
+            targets: vec![
+                (
+                    String::from("user.id"),
+                    (Value::int64_default(), 1, String::from("user_id")),
+                ),
+                (
+                    String::from("user.name"),
+                    (Value::string_default(), 2, String::from("user_name")),
+                ),
+            ],
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(3, c.0.len());
+        assert_eq!(
+            &Value::Int64(7),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::String(String::from("jane")),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
     }
 
     #[test]
-    fn test_add_to_container() {
+    pub fn test_split_date_time_container_item() {
         let mut c = DataCellRow::new();
-        let container_transricher = AddItem(DataCell::new_without_data(
-            Value::bool_default(),
-            String::from("col1"),
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("order_date"),
             0,
+            Some(Value::String(String::from("2022-10-17"))),
         ));
-        container_transricher.apply(&mut c).unwrap();
 
-        assert_eq!(1, c.0.len());
+        let div_at = SplitDateTimeItemAtIdx {
+            idx: 0,
+            targets: vec![
+                (
+                    DateTimeComponent::Year,
+                    (Value::int32_default(), 1, String::from("year")),
+                ),
+                (
+                    DateTimeComponent::Quarter,
+                    (Value::uint32_default(), 2, String::from("quarter")),
+                ),
+                (
+                    DateTimeComponent::WeekdayName,
+                    (Value::string_default(), 3, String::from("weekday")),
+                ),
+            ],
+            parse_fmt: Some(String::from("%Y-%m-%d")),
+            split_none: false,
+            delete_source_item: false,
+        };
+
+        div_at.apply(&mut c).unwrap();
+
+        assert_eq!(4, c.0.len());
+        assert_eq!(
+            &Value::Int32(2022),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::UInt32(4),
+            c.get_by_idx(2).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::String(String::from("Mon")),
+            c.get_by_idx(3).unwrap().get_data().unwrap()
+        );
     }
 
     #[test]
-    fn test_combined() {
+    pub fn test_split_container_item_with_token_policy_empty_token_into_numeric_target() {
         let mut c = DataCellRow::new();
-        c.0.push(DataCell::new_without_data(
-            Value::bool_default(),
+        c.0.push(DataCell::new(
+            Value::string_default(),
             String::from("col1"),
             0,
+            Some(Value::String(String::from("1.5; "))),
         ));
-        c.0.push(DataCell::new_without_data(
-            Value::bool_default(),
-            String::from("col2"),
-            1,
-        ));
-
-        let mut transrichers: Vec<Box<dyn TransrichContainerInplace<DataCellRow>>> =
-            Vec::with_capacity(3);
 
-        transrichers.push(Box::new(AddItem(DataCell::new_without_data(
-            Value::bool_default(),
-            String::from("col3"),
-            2,
-        ))));
-        transrichers.push(Box::new(DeleteItemAtIdx(1)));
-        transrichers.push(Box::new(DeleteItemAtIdx(2)));
-        transrichers.push(Box::new(MutateItemIdx::new(0, 10)));
+        let div_at = SplitItemAtIdx {
+            idx: 0,
+            divider: WithTokenPolicy {
+                splitter: ValueStringSeparatorCharSplit {
+                    sep_char: ';',
+                    split_none: false,
+                },
+                policy: TokenPolicy {
+                    empty_token: EmptyPolicy::AsNone,
+                    trim: true,
+                    ..TokenPolicy::default()
+                },
+            },
+            target_left: (Value::float32_default(), 1, String::from("col2")),
+            target_right: (Value::float32_default(), 2, String::from("col3")),
+            delete_source_item: false,
+        };
 
-        transrichers
-            .iter_mut()
-            .map(|t| t.apply(&mut c))
-            .collect::<Result<Vec<()>>>()
-            .unwrap();
+        div_at.apply(&mut c).unwrap();
 
-        assert_eq!(1, c.0.len());
-        assert_eq!(10, c.0.first().unwrap().get_idx());
-        assert_eq!(String::from("col1"), c.0.first().unwrap().get_name());
+        assert_eq!(
+            &Value::Float32(1.5_f32),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
     }
 
     #[test]
-    pub fn test_split_container_item_using_value_string_separator_char_divider() {
+    pub fn test_split_n_container_item_with_target_conversions() {
         let mut c = DataCellRow::new();
         c.0.push(DataCell::new(
             Value::string_default(),
             String::from("col1"),
             0,
-            Some(Value::String(String::from("foo:bar"))),
+            Some(Value::String(String::from("1.234,5;04.03.2022;J;12,5 %"))),
         ));
 
-        let div_at = SplitItemAtIdx {
+        let european = ConversionOptions {
+            number_locale: NumberLocale {
+                decimal_sep: ',',
+                thousands_sep: Some('.'),
+            },
+            date_formats: vec![String::from("%d.%m.%Y")],
+            truthy: vec![String::from("J")],
+            falsy: vec![String::from("N")],
+            strip_affixes: vec![String::from("%")],
+            ..Default::default()
+        };
+        let div_at = SplitItemAtIdxN {
             idx: 0,
-            divider: ValueStringSeparatorCharSplit {
-                sep_char: ':',
+            divider: ValueStringSeparatorCharSplitN {
+                sep_char: ';',
                 split_none: false,
+                split_none_into_num_clones: None,
             },
-            target_left: (Value::string_default(), 1, String::from("col2")),
-            target_right: (Value::string_default(), 2, String::from("Col3")),
-            delete_source_item: false,
+            targets: vec![
+                (Value::float64_default(), 1, String::from("amount")),
+                (Value::naive_date_default(), 2, String::from("date")),
+                (Value::bool_default(), 3, String::from("flag")),
+                (Value::float32_default(), 4, String::from("rate")),
+            ]
+            .into_iter()
+            .map(|item| ConvertedTarget {
+                item,
+                conversion: Some(european.clone()),
+            })
+            .collect(),
+            on_mismatch: SplitNTargetMismatch::Fail,
+            delete_source_item: true,
         };
 
         div_at.apply(&mut c).unwrap();
 
-        assert_eq!(3, c.0.len());
         assert_eq!(
-            &Value::String(String::from("foo")),
+            &Value::Float64(1234.5),
             c.get_by_idx(1).unwrap().get_data().unwrap()
         );
         assert_eq!(
-            &Value::String(String::from("bar")),
+            &Value::NaiveDate(chrono::NaiveDate::from_ymd_opt(2022, 3, 4).unwrap()),
             c.get_by_idx(2).unwrap().get_data().unwrap()
         );
+        assert_eq!(
+            &Value::Bool(true),
+            c.get_by_idx(3).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Float32(12.5),
+            c.get_by_idx(4).unwrap().get_data().unwrap()
+        );
     }
 
     #[test]
-    pub fn test_split_container_item_using_value_string_separator_char_divider_delete_src() {
+    pub fn test_split_container_item_err_leaves_container_untouched() {
         let mut c = DataCellRow::new();
         c.0.push(DataCell::new(
             Value::string_default(),
             String::from("col1"),
             0,
-            Some(Value::String(String::from("foo:bar"))),
+            Some(Value::String(String::from("foo;bar"))),
         ));
 
         let div_at = SplitItemAtIdx {
             idx: 0,
             divider: ValueStringSeparatorCharSplit {
-                sep_char: ':',
-                split_none: false,
+                sep_char: ';',
+                split_none: true,
             },
             target_left: (Value::string_default(), 1, String::from("col2")),
-            target_right: (Value::string_default(), 2, String::from("Col3")),
+            target_right: (Value::int32_default(), 2, String::from("col3")),
             delete_source_item: true,
         };
 
-        div_at.apply(&mut c).unwrap();
+        let res = div_at.apply(&mut c);
+        match res {
+            Err(VenumTdsTransRichError::Target { idx, name, .. }) => {
+                assert_eq!(2, idx);
+                assert_eq!("col3", name);
+            }
+            _ => panic!("expected a target error, got: {:?}", res),
+        }
+        assert_eq!(1, c.0.len());
+        assert_eq!(
+            &Value::String(String::from("foo;bar")),
+            c.get_by_idx(0).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_split_n_container_item_err_leaves_container_untouched() {
+        let mut c = DataCellRow::new();
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::String(String::from("1;x;3"))),
+        ));
+
+        let div_at = SplitItemAtIdxN {
+            idx: 0,
+            divider: ValueStringSeparatorCharSplitN {
+                sep_char: ';',
+                split_none: false,
+                split_none_into_num_clones: None,
+            },
+            targets: vec![
+                (Value::int32_default(), 1, String::from("a")),
+                (Value::int32_default(), 2, String::from("b")),
+                (Value::int32_default(), 3, String::from("c")),
+            ],
+            on_mismatch: SplitNTargetMismatch::Fail,
+            delete_source_item: true,
+        };
+
+        let res = div_at.apply(&mut c);
+        match res {
+            Err(VenumTdsTransRichError::Target { idx, name, .. }) => {
+                assert_eq!(2, idx);
+                assert_eq!("b", name);
+            }
+            _ => panic!("expected a target error, got: {:?}", res),
+        }
+        assert_eq!(1, c.0.len());
+        assert!(c.get_by_idx(0).is_some());
+    }
+
+    const CONFLICT_CELLS: &[(&str, usize, Option<&str>)] =
+        &[("col1", 0, Some("foo;bar")), ("col2", 1, Some("existing"))];
+
+    fn conflict_test_split(
+        on_conflict: TargetConflict,
+    ) -> OnConflict<SplitItemAtIdx<ValueStringSeparatorCharSplit>> {
+        OnConflict {
+            op: SplitItemAtIdx {
+                idx: 0,
+                divider: ValueStringSeparatorCharSplit {
+                    sep_char: ';',
+                    split_none: true,
+                },
+                target_left: (Value::string_default(), 1, String::from("left")),
+                target_right: (Value::string_default(), 2, String::from("right")),
+                delete_source_item: false,
+            },
+            on_conflict,
+        }
+    }
+
+    #[test]
+    fn test_split_container_item_without_conflict_policy_adds_targets_as_is() {
+        let mut c = test_row(CONFLICT_CELLS);
+        conflict_test_split(TargetConflict::Fail)
+            .op
+            .apply(&mut c)
+            .unwrap();
+        assert_eq!(4, c.0.len());
+        assert!(validate_unique_items(&c).is_err());
+    }
+
+    #[test]
+    fn test_split_container_item_target_conflict_fail() {
+        let mut c = test_row(CONFLICT_CELLS);
+        let res = conflict_test_split(TargetConflict::Fail).apply(&mut c);
+        assert_eq!(
+            Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::ItemConflict {
+                    idx: 1,
+                    name: String::from("left"),
+                    msg: String::from("collides with item col2 at idx 1"),
+                }
+            )),
+            res
+        );
+        assert_eq!(test_row(CONFLICT_CELLS), c);
+    }
+
+    #[test]
+    fn test_split_container_item_target_conflict_overwrite() {
+        let mut c = test_row(CONFLICT_CELLS);
+        conflict_test_split(TargetConflict::Overwrite)
+            .apply(&mut c)
+            .unwrap();
+        assert_eq!(3, c.0.len());
+        assert_eq!("left", c.get_by_idx(1).unwrap().get_name());
+        assert_eq!(
+            &Value::String(String::from("foo")),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        validate_unique_items(&c).unwrap();
+    }
+
+    #[test]
+    fn test_split_container_item_target_conflict_skip() {
+        let mut c = test_row(CONFLICT_CELLS);
+        conflict_test_split(TargetConflict::Skip)
+            .apply(&mut c)
+            .unwrap();
+        assert_eq!(3, c.0.len());
+        assert_eq!("col2", c.get_by_idx(1).unwrap().get_name());
+        assert_eq!("right", c.get_by_idx(2).unwrap().get_name());
+        validate_unique_items(&c).unwrap();
+    }
+
+    #[test]
+    fn test_split_container_item_target_conflict_shift() {
+        let mut c = test_row(CONFLICT_CELLS);
+        conflict_test_split(TargetConflict::Shift)
+            .apply(&mut c)
+            .unwrap();
+        assert_eq!(4, c.0.len());
+        assert_eq!("col1", c.get_by_idx(0).unwrap().get_name());
+        assert_eq!("left", c.get_by_idx(1).unwrap().get_name());
+        assert_eq!("right", c.get_by_idx(2).unwrap().get_name());
+        assert_eq!("col2", c.get_by_idx(3).unwrap().get_name());
+        validate_unique_items(&c).unwrap();
+    }
+
+    #[test]
+    fn test_split_container_item_target_conflict_shift_descending_targets() {
+        let mut c = test_row(CONFLICT_CELLS);
+        let mut split = conflict_test_split(TargetConflict::Shift);
+        split.op.target_left = (Value::string_default(), 2, String::from("left"));
+        split.op.target_right = (Value::string_default(), 1, String::from("right"));
+        split.apply(&mut c).unwrap();
+        assert_eq!(4, c.0.len());
+        assert_eq!("col1", c.get_by_idx(0).unwrap().get_name());
+        assert_eq!("right", c.get_by_idx(1).unwrap().get_name());
+        assert_eq!("left", c.get_by_idx(2).unwrap().get_name());
+        assert_eq!("col2", c.get_by_idx(3).unwrap().get_name());
+        validate_unique_items(&c).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ContainerOps(ItemConflict { idx: 1, name: \"right\", msg: \"another new item has the same idx. Can't shift.\" })"
+    )]
+    fn test_split_container_item_target_conflict_shift_err_same_target_idx() {
+        let mut c = test_row(CONFLICT_CELLS);
+        let mut split = conflict_test_split(TargetConflict::Shift);
+        split.op.target_right = (Value::string_default(), 1, String::from("right"));
+        split.apply(&mut c).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ContainerOps(ItemConflict { idx: 5, name: \"col1\", msg: \"an item with the same name exists. Can't shift.\" })"
+    )]
+    fn test_add_item_target_conflict_shift_err_same_name() {
+        let mut c = test_row(CONFLICT_CELLS);
+        AddItem::with_conflict(
+            DataCell::new_without_data(Value::bool_default(), String::from("col1"), 5),
+            TargetConflict::Shift,
+        )
+        .apply(&mut c)
+        .unwrap();
+    }
 
-        assert_eq!(2, c.0.len());
-        assert_eq!(
-            &Value::String(String::from("foo")),
-            c.get_by_idx(1).unwrap().get_data().unwrap()
-        );
+    #[test]
+    fn test_merge_container_items_target_reuses_deleted_source_idx() {
+        let mut c = test_row(CONFLICT_CELLS);
+        let merge = MergeItemsAs {
+            idx_a: 0,
+            idx_b: 1,
+            merger: ValueStringJoinMerge {
+                separator: String::from("|"),
+                merge_none: MergeNone::Skip,
+            },
+            target: (Value::string_default(), 0, String::from("merged")),
+            delete_source_items: true,
+        };
+        merge.apply(&mut c).unwrap();
+        assert_eq!(1, c.0.len());
         assert_eq!(
-            &Value::String(String::from("bar")),
-            c.get_by_idx(2).unwrap().get_data().unwrap()
+            &Value::String(String::from("foo;bar|existing")),
+            c.get_by_idx(0).unwrap().get_data().unwrap()
         );
     }
 
     #[test]
-    pub fn test_split_container_item_using_value_string_separator_char_divider_none() {
-        let mut c = DataCellRow::new();
+    #[should_panic(
+        expected = "ContainerOps(ItemConflict { idx: 1, name: \"col3\", msg: \"duplicate of item col2 at idx 1\" })"
+    )]
+    fn test_validate_unique_items_err_duplicate_idx() {
+        let mut c = test_row(CONFLICT_CELLS);
         c.0.push(DataCell::new_without_data(
-            Value::string_default(),
-            String::from("col1"),
-            0,
+            Value::bool_default(),
+            String::from("col3"),
+            1,
         ));
+        validate_unique_items(&c).unwrap();
+    }
 
+    #[test]
+    pub fn test_split_container_item_selected_by_name() {
+        let mut c = test_row(CONFLICT_CELLS);
         let div_at = SplitItemAtIdx {
-            idx: 0,
+            idx: ItemSelector::NameIgnoreCase(String::from("COL1")),
             divider: ValueStringSeparatorCharSplit {
-                sep_char: ':',
+                sep_char: ';',
                 split_none: true,
             },
-            target_left: (Value::string_default(), 1, String::from("col2")),
-            target_right: (Value::string_default(), 2, String::from("Col3")),
-            delete_source_item: false,
+            target_left: (Value::string_default(), 2, String::from("left")),
+            target_right: (Value::string_default(), 3, String::from("right")),
+            delete_source_item: true,
         };
-
         div_at.apply(&mut c).unwrap();
-
         assert_eq!(3, c.0.len());
-        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
-        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+        assert!(c.get_by_idx(0).is_none());
+        assert_eq!(
+            &Value::String(String::from("bar")),
+            c.get_by_idx(3).unwrap().get_data().unwrap()
+        );
     }
 
     #[test]
-    pub fn test_split_container_item_using_value_string_separator_char_divider_none_delete_src() {
-        let mut c = DataCellRow::new();
-        c.0.push(DataCell::new_without_data(
-            Value::string_default(),
-            String::from("col1"),
-            0,
+    pub fn test_merge_n_container_items_selected_by_regex_and_delete_by_type() {
+        let mut c = test_row(CONFLICT_CELLS);
+        c.0.push(DataCell::new(
+            Value::bool_default(),
+            String::from("flag"),
+            2,
+            Some(Value::Bool(true)),
         ));
-
-        let div_at = SplitItemAtIdx {
-            idx: 0,
-            divider: ValueStringSeparatorCharSplit {
-                sep_char: ':',
-                split_none: true,
+        let merge = MergeNItemsAs {
+            idxs: vec![ItemSelector::name_regex(String::from("^col\\d$")).unwrap()],
+            merger: ValueStringJoinMerge {
+                separator: String::from("|"),
+                merge_none: MergeNone::Skip,
             },
-            target_left: (Value::string_default(), 1, String::from("col2")),
-            target_right: (Value::string_default(), 2, String::from("Col3")),
-            delete_source_item: true,
+            target: (Value::string_default(), 3, String::from("merged")),
+            delete_source_items: false,
         };
+        merge.apply(&mut c).unwrap();
+        assert_eq!(
+            &Value::String(String::from("foo;bar|existing")),
+            c.get_by_idx(3).unwrap().get_data().unwrap()
+        );
 
-        div_at.apply(&mut c).unwrap();
-
-        assert_eq!(2, c.0.len());
-        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
-        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+        DeleteItemAtIdx(ItemSelector::TypeInfo(Value::string_default()))
+            .apply(&mut c)
+            .unwrap();
+        assert_eq!(1, c.0.len());
+        assert_eq!("flag", c.get_by_idx(2).unwrap().get_name());
     }
 
     #[test]
     #[should_panic(
-        expected = "Split(SplitError { msg: \"Value is None, but split_none is false\", src_val: None, details: None })"
+        expected = "ContainerOps(SelectorError { selector: \"Name(\\\"col9\\\")\", msg: \"matched no item\" })"
     )]
-    pub fn test_split_container_item_using_value_string_separator_char_divider_none_but_split_none_is_false(
-    ) {
-        let mut c = DataCellRow::new();
-        c.0.push(DataCell::new_without_data(
-            Value::string_default(),
-            String::from("col1"),
-            0,
-        ));
-
-        let div_at = SplitItemAtIdx {
-            idx: 0,
-            divider: ValueStringSeparatorCharSplit {
-                sep_char: ':',
-                split_none: false, // <--- !!!
-            },
-            target_left: (Value::string_default(), 1, String::from("col2")),
-            target_right: (Value::string_default(), 2, String::from("Col3")),
-            delete_source_item: false,
-        };
-
-        div_at.apply(&mut c).unwrap();
+    pub fn test_mutate_idx_err_selector_matched_nothing() {
+        let mut c = test_row(CONFLICT_CELLS);
+        MutateItemIdx::new(ItemSelector::from("col9"), 5)
+            .apply(&mut c)
+            .unwrap();
     }
 
     #[test]
-    pub fn test_split_container_item_using_value_string_regex_pair_divider() {
+    pub fn test_for_each_item_split_all_matching_items() {
         let mut c = DataCellRow::new();
         c.0.push(DataCell::new(
             Value::string_default(),
-            String::from("col1"),
+            String::from("age_range"),
             0,
-            Some(Value::String(String::from("1.12 2.23"))),
+            Some(Value::String(String::from("18-30"))),
+        ));
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("name"),
+            1,
+            Some(Value::String(String::from("foo-bar"))),
+        ));
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("price_range"),
+            2,
+            Some(Value::String(String::from("5-x"))),
+        ));
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from("size_range"),
+            3,
+            Some(Value::String(String::from("1-2"))),
         ));
 
-        let div_at = SplitItemAtIdx {
-            idx: 0,
-            divider: ValueStringRegexPairSplit::from(
-                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
-                true,
-            )
-            .unwrap(),
-            target_left: (Value::float32_default(), 1, String::from("col2")),
-            target_right: (Value::float32_default(), 2, String::from("Col3")),
-            delete_source_item: false,
+        let for_each = ForEachItem {
+            selector: ItemSelector::name_regex(String::from("_range$")).unwrap(),
+            op_for: |m: &MatchedItem| SplitItemAtIdx {
+                idx: m.idx,
+                divider: ValueStringSeparatorCharSplit {
+                    sep_char: '-',
+                    split_none: true,
+                },
+                target_left: m.target(Value::int32_default(), "{name}_from", 0),
+                target_right: m.target(Value::int32_default(), "{name}_to", 1),
+                delete_source_item: true,
+            },
         };
 
-        div_at.apply(&mut c).unwrap();
+        let res = for_each.apply(&mut c);
+        match res {
+            Err(VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::ItemErrors {
+                errors,
+            })) => {
+                assert_eq!(1, errors.len());
+                assert_eq!(2, errors[0].0);
+                assert_eq!("price_range", errors[0].1);
+            }
+            _ => panic!("expected per item errors, got: {:?}", res),
+        }
+        assert_eq!(4, c.0.len());
+        assert_eq!("price_range", c.get_by_idx(2).unwrap().get_name());
 
-        assert_eq!(3, c.0.len());
+        c.get_by_idx_mut(2).unwrap().data = Some(Value::String(String::from("5-9")));
+        for_each.apply(&mut c).unwrap();
+        let names: Vec<&str> = c.0.iter().map(|dc| dc.get_name()).collect();
         assert_eq!(
-            &Value::Float32(1.12_f32),
-            c.get_by_idx(1).unwrap().get_data().unwrap()
+            vec![
+                "name",
+                "age_range_from",
+                "age_range_to",
+                "price_range_from",
+                "price_range_to",
+                "size_range_from",
+                "size_range_to"
+            ],
+            names
         );
         assert_eq!(
-            &Value::Float32(2.23_f32),
-            c.get_by_idx(2).unwrap().get_data().unwrap()
+            &Value::Int32(18),
+            c.get_by_idx(4).unwrap().get_data().unwrap()
         );
+        assert_eq!(
+            &Value::Int32(9),
+            c.get_by_idx(7).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Int32(2),
+            c.get_by_idx(9).unwrap().get_data().unwrap()
+        );
+        validate_unique_items(&c).unwrap();
     }
 
+    const CAST_CELLS: &[(&str, usize, Option<&str>)] =
+        &[("qty", 0, Some("12")), ("price", 1, Some("n/a"))];
+
     #[test]
-    pub fn test_split_container_item_using_value_string_regex_pair_divider_delete_src() {
-        let mut c = DataCellRow::new();
-        c.0.push(DataCell::new(
-            Value::string_default(),
-            String::from("col1"),
-            0,
-            Some(Value::String(String::from("1.12 2.23"))),
+    fn test_rename_item() {
+        let mut c = test_row(CAST_CELLS);
+        RenameItem::new(ItemSelector::from("qty"), "quantity")
+            .apply(&mut c)
+            .unwrap();
+        assert_eq!("quantity", c.get_by_idx(0).unwrap().get_name());
+
+        let res = RenameItem::new(ItemSelector::Idx(0), "price").apply(&mut c);
+        assert!(matches!(
+            res,
+            Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::ItemConflict { idx: 1, .. }
+            ))
         ));
+        assert_eq!("quantity", c.get_by_idx(0).unwrap().get_name());
+
+        // a plain idx doesn't need to list the items, so there is no name check
+        RenameItem::new(1, "quantity").apply(&mut c).unwrap();
+        assert!(validate_unique_items(&c).is_err());
+
+        let res = RenameItem::new(7, "x").apply(&mut c);
+        assert!(matches!(
+            res,
+            Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::Generic { .. }
+            ))
+        ));
+    }
 
-        let div_at = SplitItemAtIdx {
-            idx: 0,
-            divider: ValueStringRegexPairSplit::from(
-                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
-                true,
-            )
-            .unwrap(),
-            target_left: (Value::float32_default(), 1, String::from("col2")),
-            target_right: (Value::float32_default(), 2, String::from("Col3")),
-            delete_source_item: true,
-        };
-
-        div_at.apply(&mut c).unwrap();
+    #[test]
+    fn test_cast_items_strict_is_all_or_nothing() {
+        let mut c = test_row(CAST_CELLS);
+        let res = CastItem {
+            idx: ItemSelector::TypeInfo(Value::string_default()),
+            type_info: Value::int32_default(),
+            mode: CastMode::Strict,
+            conversion: ConversionOptions::default(),
+        }
+        .apply(&mut c);
+        assert!(matches!(
+            res,
+            Err(VenumTdsTransRichError::Target { idx: 1, .. })
+        ));
+        assert_eq!(test_row(CAST_CELLS), c);
+    }
 
-        assert_eq!(2, c.0.len());
+    #[test]
+    fn test_cast_items_lenient() {
+        let mut c = test_row(CAST_CELLS);
+        CastItem {
+            idx: ItemSelector::TypeInfo(Value::string_default()),
+            type_info: Value::int32_default(),
+            mode: CastMode::Lenient,
+            conversion: ConversionOptions::default(),
+        }
+        .apply(&mut c)
+        .unwrap();
         assert_eq!(
-            &Value::Float32(1.12_f32),
-            c.get_by_idx(1).unwrap().get_data().unwrap()
+            &Value::Int32(12),
+            c.get_by_idx(0).unwrap().get_data().unwrap()
         );
         assert_eq!(
-            &Value::Float32(2.23_f32),
-            c.get_by_idx(2).unwrap().get_data().unwrap()
+            &Value::int32_default(),
+            c.get_by_idx(1).unwrap().get_type_info()
         );
+        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
     }
 
     #[test]
-    pub fn test_split_container_item_using_value_string_regex_pair_divider_none() {
-        let mut c = DataCellRow::new();
-        c.0.push(DataCell::new_without_data(
-            Value::string_default(),
-            String::from("col1"),
-            0,
+    fn test_copy_item() {
+        let mut c = test_row(CAST_CELLS);
+        CopyItem {
+            from: ItemSelector::from("qty"),
+            to: (2, String::from("qty_raw")),
+        }
+        .apply(&mut c)
+        .unwrap();
+        let copy = c.get_by_idx(2).unwrap();
+        assert_eq!("qty_raw", copy.get_name());
+        assert_eq!(c.get_by_idx(0).unwrap().get_data(), copy.get_data());
+
+        let res = OnConflict {
+            op: CopyItem {
+                from: ItemSelector::from("qty"),
+                to: (3, String::from("price")),
+            },
+            on_conflict: TargetConflict::Fail,
+        }
+        .apply(&mut c);
+        assert!(matches!(
+            res,
+            Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::ItemConflict { .. }
+            ))
         ));
+        assert_eq!(3, c.0.len());
+    }
 
-        let div_at = SplitItemAtIdx {
-            idx: 0,
-            divider: ValueStringRegexPairSplit::from(
-                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
-                true,
-            )
-            .unwrap(),
-            target_left: (Value::float32_default(), 1, String::from("col2")),
-            target_right: (Value::float32_default(), 2, String::from("Col3")),
-            delete_source_item: false,
-        };
+    const ORDER_CELLS: &[(&str, usize, Option<&str>)] = &[
+        ("d", 9, None),
+        ("a", 0, None),
+        ("c", 5, None),
+        ("b", 2, None),
+    ];
 
-        div_at.apply(&mut c).unwrap();
+    fn names_and_idxs(c: &DataCellRow) -> Vec<(&str, usize)> {
+        c.0.iter().map(|e| (e.get_name(), e.get_idx())).collect()
+    }
 
-        assert_eq!(3, c.0.len());
-        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
-        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+    #[test]
+    fn test_move_item() {
+        let mut c = test_row(ORDER_CELLS);
+        MoveItem {
+            from: ItemSelector::from("d"),
+            to: 1,
+        }
+        .apply(&mut c)
+        .unwrap();
+        assert_eq!(
+            vec![("a", 0), ("d", 1), ("b", 3), ("c", 6)],
+            names_and_idxs(&c)
+        );
+
+        MoveItem {
+            from: ItemSelector::from("a"),
+            to: 3,
+        }
+        .apply(&mut c)
+        .unwrap();
+        assert_eq!(
+            vec![("d", 0), ("b", 2), ("a", 3), ("c", 6)],
+            names_and_idxs(&c)
+        );
     }
 
     #[test]
-    pub fn test_split_container_item_using_value_string_regex_pair_divider_none_delete_src() {
-        let mut c = DataCellRow::new();
-        c.0.push(DataCell::new_without_data(
-            Value::string_default(),
-            String::from("col1"),
-            0,
-        ));
+    fn test_swap_items() {
+        let mut c = test_row(ORDER_CELLS);
+        SwapItems {
+            a: ItemSelector::from("a"),
+            b: ItemSelector::from("d"),
+        }
+        .apply(&mut c)
+        .unwrap();
+        assert_eq!(
+            vec![("d", 0), ("b", 2), ("c", 5), ("a", 9)],
+            names_and_idxs(&c)
+        );
+    }
 
-        let div_at = SplitItemAtIdx {
-            idx: 0,
-            divider: ValueStringRegexPairSplit::from(
-                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
-                true,
-            )
-            .unwrap(),
-            target_left: (Value::float32_default(), 1, String::from("col2")),
-            target_right: (Value::float32_default(), 2, String::from("Col3")),
-            delete_source_item: true,
-        };
+    #[test]
+    fn test_reorder_items() {
+        let names = vec![String::from("c"), String::from("a")];
 
-        div_at.apply(&mut c).unwrap();
+        let mut c = test_row(ORDER_CELLS);
+        let res = ReorderItems {
+            names: names.clone(),
+            unlisted: UnlistedItems::Fail,
+        }
+        .apply(&mut c);
+        assert!(res.is_err());
+        assert_eq!(test_row(ORDER_CELLS), c);
 
-        assert_eq!(2, c.0.len());
-        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
-        assert_eq!(None, c.get_by_idx(2).unwrap().get_data());
+        ReorderItems {
+            names: names.clone(),
+            unlisted: UnlistedItems::Append,
+        }
+        .apply(&mut c)
+        .unwrap();
+        assert_eq!(
+            vec![("c", 0), ("a", 1), ("b", 2), ("d", 3)],
+            names_and_idxs(&c)
+        );
+
+        let mut c = test_row(ORDER_CELLS);
+        ReorderItems {
+            names,
+            unlisted: UnlistedItems::Delete,
+        }
+        .apply(&mut c)
+        .unwrap();
+        assert_eq!(vec![("c", 0), ("a", 1)], names_and_idxs(&c));
     }
 
     #[test]
-    #[should_panic(
-        expected = "Split(SplitError { msg: \"Value is None, but split_none is false\", src_val: None, details: None })"
-    )]
-    pub fn test_split_container_item_using_value_string_regex_pair_divider_none_err() {
-        let mut c = DataCellRow::new();
-        c.0.push(DataCell::new_without_data(
-            Value::string_default(),
-            String::from("col1"),
-            0,
-        ));
-
-        let div_at = SplitItemAtIdx {
-            idx: 0,
-            divider: ValueStringRegexPairSplit::from(
-                "(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(),
-                false,
-            )
-            .unwrap(),
-            target_left: (Value::float32_default(), 1, String::from("col2")),
-            target_right: (Value::float32_default(), 2, String::from("Col3")),
-            delete_source_item: false,
-        };
+    #[should_panic(expected = "Name a is listed more than once. Can't reorder.")]
+    fn test_reorder_items_err_duplicate_name() {
+        ReorderItems {
+            names: vec![String::from("a"), String::from("a")],
+            unlisted: UnlistedItems::Append,
+        }
+        .apply(&mut test_row(ORDER_CELLS))
+        .unwrap();
+    }
 
-        div_at.apply(&mut c).unwrap();
+    #[test]
+    fn test_compact_item_idxs() {
+        let mut c = test_row(ORDER_CELLS);
+        CompactItemIdxs.apply(&mut c).unwrap();
+        assert_eq!(
+            vec![("a", 0), ("b", 1), ("c", 2), ("d", 3)],
+            names_and_idxs(&c)
+        );
     }
 }
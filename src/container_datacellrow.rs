@@ -0,0 +1,13 @@
+use venum_tds::{cell::DataCell, row::DataCellRow};
+
+use crate::traits::container::TransrichContainerItems;
+
+impl TransrichContainerItems for DataCellRow {
+    fn items(&self) -> &[DataCell] {
+        &self.0
+    }
+
+    fn items_mut(&mut self) -> &mut [DataCell] {
+        &mut self.0
+    }
+}
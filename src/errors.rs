@@ -1,29 +1,81 @@
-use strum_macros::Display;
 use thiserror::Error;
 
 use venum::{errors::VenumError, venum::Value};
 use venum_tds::errors::VenumTdsError;
 
-#[derive(Debug, PartialEq, Display, Clone)]
+#[derive(Error, Debug, PartialEq, Clone)]
 pub enum WrappedErrors {
+    #[error("{0:?}")]
     VenumError(VenumError),
+    #[error("{0:?}")]
     VenumTdsError(VenumTdsError),
 }
 
-#[derive(Debug, PartialEq, Display, Clone)]
+#[derive(Error, Debug, PartialEq, Clone)]
 pub enum ContainerOpsErrors {
+    #[error("{msg}")]
     Generic { msg: String },
+    #[error("item at idx {idx}: {msg}")]
     DivideItemError { idx: usize, msg: String },
+    #[error("item at idx {idx}: {msg}")]
+    MergeItemsError { idx: usize, msg: String },
+    #[error("item '{name}' at idx {idx}: {msg}")]
+    ItemConflict {
+        idx: usize,
+        name: String,
+        msg: String,
+    },
+    #[error("{selector}: {msg}")]
+    SelectorError { selector: String, msg: String },
+    /// Errors of an operation applied to several items: (idx, name, error) per failed item.
+    #[error("{}", item_errors_msg(errors))]
+    ItemErrors {
+        errors: Vec<(usize, String, VenumTdsTransRichError)>,
+    },
+}
+
+fn item_errors_msg(errors: &[(usize, String, VenumTdsTransRichError)]) -> String {
+    errors
+        .iter()
+        .map(|(idx, name, e)| format!("item '{}' at idx {}: {}", name, idx, e))
+        .collect::<Vec<String>>()
+        .join("; ")
 }
 
-#[derive(Debug, PartialEq, Display, Clone)]
+#[derive(Error, Debug, PartialEq, Clone)]
 pub enum VenumTdsTransRichError {
+    #[error("{msg}")]
     Generic { msg: String },
+    #[error("{0}")]
     Wrapped(WrappedErrors),
+    #[error("{}", msg_with_details(&.0.msg, &.0.details))]
     Split(SplitError),
+    #[error("{}", msg_with_details(&.0.msg, &.0.details))]
+    Merge(MergeError),
+    #[error("{}", msg_with_details(&.0.msg, &.0.details))]
+    Conversion(ConversionError),
+    /// Putting a value into the target item `name` at `idx` failed. None of the targets of the
+    /// operation have been modified.
+    #[error("target '{name}' at idx {idx}: {cause}")]
+    Target {
+        idx: usize,
+        name: String,
+        cause: Box<VenumTdsTransRichError>,
+    },
+    /// An invalid pipeline config. `location` is the path to the offending field, e.g. `steps[2].op`.
+    #[error("{location}: {msg}")]
+    Config { location: String, msg: String },
+    #[error("{0}")]
     ContainerOps(ContainerOpsErrors),
 }
 
+fn msg_with_details(msg: &str, details: &Option<String>) -> String {
+    match details {
+        Some(details) => format!("{} ({})", msg, details),
+        None => String::from(msg),
+    }
+}
+
 #[derive(Error, Debug, PartialEq, Clone)]
 #[error("error: {msg:?}; problem value: {src_val:?}. Details: {details:?}")]
 pub struct SplitError {
@@ -49,6 +101,56 @@ impl SplitError {
     }
 }
 
+#[derive(Error, Debug, PartialEq, Clone)]
+#[error("error: {msg:?}; problem values: {src_vals:?}. Details: {details:?}")]
+pub struct MergeError {
+    msg: String,
+    src_vals: Vec<Option<Value>>,
+    details: Option<String>,
+}
+
+impl MergeError {
+    pub fn minim(msg: String) -> Self {
+        Self {
+            msg,
+            src_vals: Vec::new(),
+            details: None,
+        }
+    }
+    pub fn from(msg: String, src_vals: Vec<Option<Value>>, details: Option<String>) -> Self {
+        Self {
+            msg,
+            src_vals,
+            details,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Clone)]
+#[error("error: {msg:?}; problem value: {src_val:?}. Details: {details:?}")]
+pub struct ConversionError {
+    msg: String,
+    src_val: Option<Value>,
+    details: Option<String>,
+}
+
+impl ConversionError {
+    pub fn minim(msg: String) -> Self {
+        Self {
+            msg,
+            src_val: None,
+            details: None,
+        }
+    }
+    pub fn from(msg: String, src_val: Option<Value>, details: Option<String>) -> Self {
+        Self {
+            msg,
+            src_val,
+            details,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, VenumTdsTransRichError>;
 
 impl From<VenumTdsError> for VenumTdsTransRichError {
@@ -3,58 +3,243 @@ use venum_tds::cell::DataCell;
 
 use crate::{
     errors::{Result, SplitError, VenumTdsTransRichError},
-    traits::{item::SplitUsing, value::Split},
+    traits::{
+        item::{
+            CastMode, CastTo, MergeNUsing, MergeUsing, SplitKeyedUsing, SplitNTargetMismatch,
+            SplitNUsing, SplitUsing, UnknownKeys,
+        },
+        value::{Merge, MergeN, Split, SplitKeyed, SplitN},
+    },
+    value_conversion::{convert_with, value_to_string, ConversionOptions, TargetConversions},
 };
 
+/// Converts `val` for `dst`, without touching `dst` yet. Errors name the target.
+fn convert_into(
+    val: &Value,
+    dst: &DataCell,
+    conversions: &TargetConversions,
+) -> Result<Option<Value>> {
+    convert_with(val, &dst.type_info, conversions.for_idx(dst.idx)).map_err(|e| {
+        VenumTdsTransRichError::Target {
+            idx: dst.idx,
+            name: dst.name.clone(),
+            cause: Box::new(e),
+        }
+    })
+}
+
 impl<D: Split> SplitUsing<D> for DataCell {
     type ITEM = Self;
 
-    fn split_using(
+    fn split_using_with(
         &self,
         splitter_impl: &D,
         dst_left: &mut DataCell,
         dst_right: &mut DataCell,
+        conversions: &TargetConversions,
     ) -> Result<()> {
         let (split_res_left, split_res_right) = splitter_impl.split(&self.data)?;
 
-        fn converse_to(val: &Value, type_info: &Value) -> Result<Option<Value>> {
-            match val {
-                // we have the same enum variant in src and dst, we can use/clone it as is
-                _ if std::mem::discriminant(val) == std::mem::discriminant(type_info) => {
-                    Ok(Some(val.clone()))
-                }
-                // we have a String variant as src type try converting it to the target type
-                Value::String(s) => {
-                    let transf_val = Value::from_string_with_templ(s, type_info)?;
-                    Ok(transf_val)
-                }
-                // TODO We can do better, but we don't support arbitrary convertions for now...
-                _ => Err(VenumTdsTransRichError::Split(SplitError::from(
-                    format!("type mismatch. {val:?} cannot be parsed/converted/put into destination of type {type_info:?}"),
-                    Some(val.clone()),
-                    None,
-                ))),
+        // convert both tokens, before touching any target
+        let data_left = split_res_left
+            .map(|data| convert_into(&data, dst_left, conversions))
+            .transpose()?;
+        let data_right = split_res_right
+            .map(|data| convert_into(&data, dst_right, conversions))
+            .transpose()?;
+
+        if let Some(data) = data_left {
+            dst_left.data = data;
+        }
+        if let Some(data) = data_right {
+            dst_right.data = data;
+        }
+        Ok(())
+    }
+}
+
+impl<D: SplitN> SplitNUsing<D> for DataCell {
+    type ITEM = Self;
+
+    fn split_n_using_with(
+        &self,
+        splitter_impl: &D,
+        dsts: &mut [DataCell],
+        on_mismatch: &SplitNTargetMismatch,
+        conversions: &TargetConversions,
+    ) -> Result<()> {
+        let mut split_res = splitter_impl.split_n(&self.data)?;
+
+        let num_targets = dsts.len();
+        let num_tokens = split_res.len();
+        match (num_tokens.cmp(&num_targets), on_mismatch) {
+            (std::cmp::Ordering::Equal, _) => {}
+            (std::cmp::Ordering::Less, SplitNTargetMismatch::PadWithNone) => {
+                split_res.resize(num_targets, None)
             }
+            (std::cmp::Ordering::Greater, SplitNTargetMismatch::FoldTailIntoLast(sep))
+                if num_targets > 0 =>
+            {
+                let tail = split_res.split_off(num_targets - 1);
+                let folded = tail
+                    .iter()
+                    .map(|v| v.as_ref().map(value_to_string).unwrap_or_default())
+                    .collect::<Vec<String>>()
+                    .join(sep);
+                split_res.push(Some(Value::String(folded)));
+            }
+            _ => {
+                return Err(VenumTdsTransRichError::Split(SplitError::from(
+                    format!(
+                        "expected {} tokens as result of split, but got: {}",
+                        num_targets, num_tokens
+                    ),
+                    self.data.clone(),
+                    Some(format!("on_mismatch: {:?}", on_mismatch)),
+                )))
+            }
+        }
+
+        let converted = split_res
+            .iter()
+            .zip(dsts.iter())
+            .map(|(token, dst)| match token {
+                Some(data) => convert_into(data, dst, conversions),
+                None => Ok(None),
+            })
+            .collect::<Result<Vec<Option<Value>>>>()?;
+
+        for (dst, data) in dsts.iter_mut().zip(converted) {
+            dst.data = data;
         }
+        Ok(())
+    }
+}
+
+impl<D: SplitKeyed> SplitKeyedUsing<D> for DataCell {
+    type ITEM = Self;
+
+    fn split_keyed_using_with(
+        &self,
+        splitter_impl: &D,
+        dsts: &mut [(String, Option<Value>, DataCell)],
+        unknown_keys: UnknownKeys<&mut DataCell>,
+        conversions: &TargetConversions,
+    ) -> Result<()> {
+        let split_res = splitter_impl.split_keyed(&self.data)?;
 
-        match (split_res_left, split_res_right) {
-            (Some(ref data_left), Some(ref data_right)) => {
-                dst_left.data = converse_to(data_left, &dst_left.type_info)?;
-                dst_right.data = converse_to(data_right, &dst_right.type_info)?;
+        let mut found: Vec<Option<Option<Value>>> = vec![None; dsts.len()];
+        let mut unknown: Vec<(String, Option<Value>)> = Vec::new();
+        for (key, val) in split_res {
+            match dsts.iter().position(|(k, _, _)| *k == key) {
+                Some(pos) => found[pos] = Some(val),
+                None => unknown.push((key, val)),
             }
-            (Some(ref data_left), None) => {
-                dst_left.data = converse_to(data_left, &dst_left.type_info)?
+        }
+
+        let converted = dsts
+            .iter()
+            .zip(found)
+            .map(
+                |((_, default, dst), val)| match val.unwrap_or_else(|| default.clone()) {
+                    Some(ref data) => convert_into(data, dst, conversions),
+                    None => Ok(None),
+                },
+            )
+            .collect::<Result<Vec<Option<Value>>>>()?;
+
+        let remainder = match unknown_keys {
+            UnknownKeys::Fail if !unknown.is_empty() => {
+                return Err(VenumTdsTransRichError::Split(SplitError::from(
+                    format!(
+                        "unknown key(s): {:?}",
+                        unknown.iter().map(|(k, _)| k).collect::<Vec<&String>>()
+                    ),
+                    self.data.clone(),
+                    None,
+                )))
             }
-            (None, Some(ref data_right)) => {
-                dst_right.data = converse_to(data_right, &dst_right.type_info)?
+            UnknownKeys::CollectInto(dst) if !unknown.is_empty() => {
+                let data = convert_into(&splitter_impl.join_keyed(&unknown), dst, conversions)?;
+                Some((dst, data))
             }
-            (None, None) => {}
+            _ => None,
+        };
+
+        for ((_, _, dst), data) in dsts.iter_mut().zip(converted) {
+            dst.data = data;
+        }
+        if let Some((dst, data)) = remainder {
+            dst.data = data;
+        }
+        Ok(())
+    }
+}
+
+impl<M: Merge> MergeUsing<M> for DataCell {
+    type ITEM = Self;
+
+    fn merge_using_with(
+        &self,
+        merge_impl: &M,
+        src_other: &DataCell,
+        dst: &mut DataCell,
+        conversions: &TargetConversions,
+    ) -> Result<()> {
+        if let Some(ref data) = merge_impl.merge(&self.data, &src_other.data)? {
+            dst.data = convert_into(data, dst, conversions)?;
+        }
+        Ok(())
+    }
+}
+
+impl<M: MergeN> MergeNUsing<M> for DataCell {
+    type ITEM = Self;
+
+    fn merge_n_using_with(
+        &self,
+        merge_impl: &M,
+        src_others: Vec<&DataCell>,
+        dst: &mut DataCell,
+        conversions: &TargetConversions,
+    ) -> Result<()> {
+        let mut src = Vec::with_capacity(src_others.len() + 1);
+        src.push(&self.data);
+        src.extend(src_others.into_iter().map(|dc| &dc.data));
+
+        if let Some(ref data) = merge_impl.merge_n(src)? {
+            dst.data = convert_into(data, dst, conversions)?;
         }
         Ok(())
     }
 }
 
-// TODO: merge
+impl CastTo for DataCell {
+    fn cast_to(
+        &mut self,
+        type_info: &Value,
+        mode: CastMode,
+        conversion: &ConversionOptions,
+    ) -> Result<()> {
+        let data = match &self.data {
+            Some(data) => match (convert_with(data, type_info, conversion), mode) {
+                (Ok(converted), _) => converted,
+                (Err(_), CastMode::Lenient) => None,
+                (Err(e), CastMode::Strict) => {
+                    return Err(VenumTdsTransRichError::Target {
+                        idx: self.idx,
+                        name: self.name.clone(),
+                        cause: Box::new(e),
+                    })
+                }
+            },
+            None => None,
+        };
+        self.type_info = type_info.clone();
+        self.data = data;
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -62,8 +247,13 @@ mod tests {
     use venum_tds::{cell::DataCell, traits::VDataContainerItem};
 
     use crate::{
-        traits::item::SplitUsing,
-        value_splitting::{ValueStringRegexPairSplit, ValueStringSeparatorCharSplit},
+        errors::VenumTdsTransRichError,
+        traits::item::{CastMode, CastTo, MergeNUsing, MergeUsing, SplitUsing},
+        value_conversion::ConversionOptions,
+        value_merging::{MergeNone, ValueStringConcatMerge, ValueStringJoinMerge},
+        value_splitting::{
+            ValueNumberFractionSplit, ValueStringRegexPairSplit, ValueStringSeparatorCharSplit,
+        },
     };
 
     #[test]
@@ -122,7 +312,161 @@ mod tests {
         assert!(dc_left.get_data().is_some());
         assert!(dc_right.get_data().is_some());
 
-        assert_eq!(1.12f32, dc_left.get_data().unwrap().try_into().unwrap());
-        assert_eq!(2.23f32, dc_right.get_data().unwrap().try_into().unwrap());
+        assert_eq!(1.12f32, f32::try_from(dc_left.get_data().unwrap()).unwrap());
+        assert_eq!(
+            2.23f32,
+            f32::try_from(dc_right.get_data().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_split_datacell_typed_tokens_into_differently_typed_targets() {
+        let dc1 = DataCell::new(
+            Value::float64_default(),
+            String::from("amount"),
+            0,
+            Some(Value::Float64(-12.25)),
+        );
+
+        let sp = ValueNumberFractionSplit { split_none: true };
+
+        let mut dc_left =
+            DataCell::new_without_data(Value::int16_default(), String::from("int_part"), 1);
+        let mut dc_right =
+            DataCell::new_without_data(Value::string_default(), String::from("fract_part"), 2);
+
+        let res = dc1.split_using(&sp, &mut dc_left, &mut dc_right);
+        assert!(res.is_ok());
+        assert_eq!(Some(&Value::Int16(-12)), dc_left.get_data());
+        assert_eq!(
+            Some(&Value::from(String::from("-0.25"))),
+            dc_right.get_data()
+        );
+    }
+
+    #[test]
+    fn test_split_datacell_err_leaves_targets_untouched() {
+        let dc1 = DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::from(String::from("true;abc"))),
+        );
+
+        let sp = ValueStringSeparatorCharSplit {
+            sep_char: ';',
+            split_none: true,
+        };
+
+        let mut dc_left =
+            DataCell::new_without_data(Value::bool_default(), String::from("is_true"), 1);
+        let mut dc_right =
+            DataCell::new_without_data(Value::float32_default(), String::from("f32_val"), 2);
+
+        let res = dc1.split_using(&sp, &mut dc_left, &mut dc_right);
+        match res {
+            Err(VenumTdsTransRichError::Target { idx, name, .. }) => {
+                assert_eq!(2, idx);
+                assert_eq!("f32_val", name);
+            }
+            _ => panic!("expected a target error, got: {:?}", res),
+        }
+        assert!(dc_left.get_data().is_none());
+        assert!(dc_right.get_data().is_none());
+    }
+
+    #[test]
+    fn test_merge_datacells_by_concat_merge() {
+        let dc1 = DataCell::new(
+            Value::string_default(),
+            String::from("int_part"),
+            0,
+            Some(Value::from(String::from("42"))),
+        );
+        let dc2 = DataCell::new(
+            Value::string_default(),
+            String::from("unit"),
+            1,
+            Some(Value::from(String::from("7"))),
+        );
+
+        let m = ValueStringConcatMerge {
+            merge_none: MergeNone::Skip,
+        };
+
+        let mut dc_dst =
+            DataCell::new_without_data(Value::float32_default(), String::from("f32_val"), 2);
+
+        let res = dc1.merge_using(&m, &dc2, &mut dc_dst);
+        assert!(res.is_ok());
+        assert_eq!(427f32, f32::try_from(dc_dst.get_data().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_merge_n_datacells_by_join_merge() {
+        let dc1 = DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::from(String::from("a"))),
+        );
+        let dc2 = DataCell::new_without_data(Value::string_default(), String::from("col2"), 1);
+        let dc3 = DataCell::new(
+            Value::string_default(),
+            String::from("col3"),
+            2,
+            Some(Value::from(String::from("c"))),
+        );
+
+        let m = ValueStringJoinMerge {
+            separator: String::from("-"),
+            merge_none: MergeNone::AsEmpty,
+        };
+
+        let mut dc_dst =
+            DataCell::new_without_data(Value::string_default(), String::from("key"), 3);
+
+        let res = dc1.merge_n_using(&m, vec![&dc2, &dc3], &mut dc_dst);
+        assert!(res.is_ok());
+        assert_eq!(Some(&Value::from(String::from("a--c"))), dc_dst.get_data());
+    }
+
+    #[test]
+    fn test_cast_datacell_strict_and_lenient() {
+        let mut dc = DataCell::new(
+            Value::string_default(),
+            String::from("col1"),
+            0,
+            Some(Value::from(String::from("42"))),
+        );
+        dc.cast_to(
+            &Value::uint8_default(),
+            CastMode::Strict,
+            &ConversionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(&Value::uint8_default(), dc.get_type_info());
+        assert_eq!(Some(&Value::UInt8(42)), dc.get_data());
+
+        let res = dc.cast_to(
+            &Value::bool_default(),
+            CastMode::Strict,
+            &ConversionOptions::default(),
+        );
+        assert!(matches!(
+            res,
+            Err(VenumTdsTransRichError::Target { idx: 0, .. })
+        ));
+        assert_eq!(&Value::uint8_default(), dc.get_type_info());
+        assert_eq!(Some(&Value::UInt8(42)), dc.get_data());
+
+        dc.cast_to(
+            &Value::bool_default(),
+            CastMode::Lenient,
+            &ConversionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(&Value::bool_default(), dc.get_type_info());
+        assert_eq!(None, dc.get_data());
     }
 }
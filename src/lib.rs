@@ -1,5 +1,14 @@
+pub mod config;
 pub mod container;
+pub mod container_datacellrow;
 pub mod errors;
 pub mod item_datacell;
+pub mod pipeline;
+pub mod schema;
+pub mod selector;
+#[cfg(test)]
+mod test_util;
 pub mod traits;
+pub mod value_conversion;
+pub mod value_merging;
 pub mod value_splitting;
@@ -0,0 +1,321 @@
+use venum_tds::traits::VDataContainer;
+
+use crate::{
+    errors::{Result, VenumTdsTransRichError},
+    traits::container::TransrichContainerInplace,
+};
+
+/// What to do, if a step of a `Pipeline` fails.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepErrorPolicy {
+    /// Stop the pipeline, the row failed. The container is left as it was after the failing step
+    /// and should be discarded.
+    FailRow,
+    /// Go on with the next step, as if the failing one wasn't there.
+    SkipStep,
+    /// Apply the step to the row as if it was a schema (see
+    /// `TransrichContainerInplace::apply_schema`), i.e. delete the items the step deletes on
+    /// success and add its targets without data, and go on with the next step. This keeps the
+    /// shape of the row the same as if the step had succeeded. If that fails as well, e.g. because
+    /// the targets collide with existing items, the row fails. For a step without targets (see
+    /// `TransrichContainerInplace::has_targets`), e.g. a `CastItem`, there is nothing to null out
+    /// and this is the same as `FailRow`.
+    NullTargets,
+}
+
+pub struct PipelineStep<C> {
+    pub name: String,
+    pub op: Box<dyn TransrichContainerInplace<C>>,
+    pub on_error: StepErrorPolicy,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepStatus {
+    Applied,
+    /// The step failed and was skipped (`StepErrorPolicy::SkipStep`).
+    Skipped(VenumTdsTransRichError),
+    /// The step failed and its targets were nulled out (`StepErrorPolicy::NullTargets`).
+    TargetsNulled(VenumTdsTransRichError),
+    /// The step failed (or nulling out its targets did) and the row failed with it.
+    RowFailed(VenumTdsTransRichError),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepOutcome {
+    pub step: String,
+    pub status: StepStatus,
+}
+
+/// The outcome of applying a `Pipeline` to one row (container): one entry per step that ran, in
+/// order. Steps after a step with `StepStatus::RowFailed` didn't run and are not listed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RowOutcome {
+    pub steps: Vec<StepOutcome>,
+}
+
+impl RowOutcome {
+    pub fn is_failed(&self) -> bool {
+        self.failure().is_some()
+    }
+
+    /// The step that failed the row and why, if any.
+    pub fn failure(&self) -> Option<(&str, &VenumTdsTransRichError)> {
+        self.steps.iter().find_map(|s| match &s.status {
+            StepStatus::RowFailed(e) => Some((s.step.as_str(), e)),
+            _ => None,
+        })
+    }
+
+    /// All steps that failed and why, including the skipped and nulled out ones.
+    pub fn errors(&self) -> Vec<(&str, &VenumTdsTransRichError)> {
+        self.steps
+            .iter()
+            .filter_map(|s| match &s.status {
+                StepStatus::Applied => None,
+                StepStatus::Skipped(e)
+                | StepStatus::TargetsNulled(e)
+                | StepStatus::RowFailed(e) => Some((s.step.as_str(), e)),
+            })
+            .collect()
+    }
+}
+
+/// Named transformation steps, applied in order to a container.
+pub struct Pipeline<C> {
+    pub steps: Vec<PipelineStep<C>>,
+}
+
+impl<C> Default for Pipeline<C> {
+    fn default() -> Self {
+        Self { steps: Vec::new() }
+    }
+}
+
+impl<C: VDataContainer> Pipeline<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_step<OP>(mut self, name: &str, op: OP, on_error: StepErrorPolicy) -> Self
+    where
+        OP: TransrichContainerInplace<C> + 'static,
+    {
+        self.steps.push(PipelineStep {
+            name: String::from(name),
+            op: Box::new(op),
+            on_error,
+        });
+        self
+    }
+
+    /// Applies all steps to the container, according to their error policies.
+    pub fn apply_row(&self, container: &mut C) -> RowOutcome {
+        self.run(container, false)
+    }
+
+    /// Like `apply_row`, but applies the steps to a schema (see
+    /// `TransrichContainerInplace::apply_schema`). Any error in the outcome is an error of the
+    /// pipeline (for the schema), that would occur for every row. Afterwards, `schema` is the
+    /// schema of the output.
+    pub fn check_schema(&self, schema: &mut C) -> RowOutcome {
+        self.run(schema, true)
+    }
+
+    fn run(&self, container: &mut C, schema: bool) -> RowOutcome {
+        let mut outcome = RowOutcome::default();
+        for step in &self.steps {
+            let res = match schema {
+                true => step.op.apply_schema(container),
+                false => step.op.apply(container),
+            };
+            let status = match (res, &step.on_error) {
+                (Ok(()), _) => StepStatus::Applied,
+                (Err(e), StepErrorPolicy::FailRow) => StepStatus::RowFailed(e),
+                (Err(e), StepErrorPolicy::SkipStep) => StepStatus::Skipped(e),
+                (Err(e), StepErrorPolicy::NullTargets) if !step.op.has_targets() => {
+                    StepStatus::RowFailed(e)
+                }
+                (Err(e), StepErrorPolicy::NullTargets) => match step.op.apply_schema(container) {
+                    Ok(()) => StepStatus::TargetsNulled(e),
+                    Err(null_err) => StepStatus::RowFailed(null_err),
+                },
+            };
+            let failed = matches!(status, StepStatus::RowFailed(_));
+            outcome.steps.push(StepOutcome {
+                step: step.name.clone(),
+                status,
+            });
+            if failed {
+                break;
+            }
+        }
+        outcome
+    }
+}
+
+/// A pipeline is itself an op, failing with the error of the step that failed the row.
+impl<C: VDataContainer> TransrichContainerInplace<C> for Pipeline<C> {
+    fn apply(&self, container: &mut C) -> Result<()> {
+        match self.apply_row(container).failure() {
+            Some((_, e)) => Err(e.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn apply_schema(&self, schema: &mut C) -> Result<()> {
+        match self.check_schema(schema).failure() {
+            Some((_, e)) => Err(e.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn has_targets(&self) -> bool {
+        self.steps.iter().any(|step| step.op.has_targets())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use venum::venum::Value;
+    use venum_tds::{row::DataCellRow, traits::VDataContainerItem};
+
+    use crate::{
+        container::{CastItem, DeleteItemAtIdx, MutateItemIdx, SplitItemAtIdx, TargetItem},
+        selector::ItemSelector,
+        test_util::test_row,
+        traits::item::CastMode,
+        value_conversion::ConversionOptions,
+        value_splitting::ValueStringSeparatorCharSplit,
+    };
+
+    use super::*;
+
+    fn targets() -> Vec<TargetItem> {
+        vec![
+            (Value::int32_default(), 1, String::from("from")),
+            (Value::int32_default(), 2, String::from("to")),
+        ]
+    }
+
+    fn split_range() -> SplitItemAtIdx<ValueStringSeparatorCharSplit, ItemSelector> {
+        let [left, right]: [TargetItem; 2] = targets().try_into().unwrap();
+        SplitItemAtIdx {
+            idx: ItemSelector::from("range"),
+            divider: ValueStringSeparatorCharSplit {
+                sep_char: '-',
+                split_none: false,
+            },
+            target_left: left,
+            target_right: right,
+            delete_source_item: true,
+        }
+    }
+
+    fn pipeline(on_split_error: StepErrorPolicy) -> Pipeline<DataCellRow> {
+        Pipeline::new()
+            .with_step("split range", split_range(), on_split_error)
+            .with_step(
+                "move to",
+                MutateItemIdx::new(ItemSelector::from("to"), 3),
+                StepErrorPolicy::FailRow,
+            )
+    }
+
+    #[test]
+    fn test_pipeline_applies_steps_in_order() {
+        let mut c = test_row(&[("range", 0, Some("3-7"))]);
+        let outcome = pipeline(StepErrorPolicy::FailRow).apply_row(&mut c);
+        assert!(!outcome.is_failed());
+        assert!(outcome.errors().is_empty());
+        assert_eq!(2, outcome.steps.len());
+        assert_eq!(
+            &Value::Int32(3),
+            c.get_by_idx(1).unwrap().get_data().unwrap()
+        );
+        assert_eq!(
+            &Value::Int32(7),
+            c.get_by_idx(3).unwrap().get_data().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pipeline_fail_row() {
+        let mut c = test_row(&[("range", 0, Some("nope"))]);
+        let outcome = pipeline(StepErrorPolicy::FailRow).apply_row(&mut c);
+        assert_eq!(1, outcome.steps.len());
+        assert_eq!("split range", outcome.failure().unwrap().0);
+        assert!(pipeline(StepErrorPolicy::FailRow)
+            .apply(&mut test_row(&[("range", 0, Some("nope"))]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_pipeline_skip_step() {
+        let mut c = test_row(&[("range", 0, Some("nope"))]);
+        let outcome = Pipeline::new()
+            .with_step("split range", split_range(), StepErrorPolicy::SkipStep)
+            .with_step(
+                "delete range",
+                DeleteItemAtIdx(ItemSelector::from("range")),
+                StepErrorPolicy::FailRow,
+            )
+            .apply_row(&mut c);
+        assert!(!outcome.is_failed());
+        assert_eq!(1, outcome.errors().len());
+        assert!(matches!(outcome.steps[0].status, StepStatus::Skipped(_)));
+        assert_eq!(StepStatus::Applied, outcome.steps[1].status);
+        assert_eq!(0, c.0.len());
+    }
+
+    #[test]
+    fn test_pipeline_null_targets() {
+        let mut c = test_row(&[("range", 0, Some("nope"))]);
+        let outcome = pipeline(StepErrorPolicy::NullTargets).apply_row(&mut c);
+        assert!(!outcome.is_failed());
+        assert!(matches!(
+            outcome.steps[0].status,
+            StepStatus::TargetsNulled(_)
+        ));
+        // same shape as on success: the source is deleted
+        assert_eq!(2, c.0.len());
+        assert_eq!(None, c.get_by_idx(1).unwrap().get_data());
+        let to = c.get_by_idx(3).unwrap();
+        assert_eq!("to", to.get_name());
+        assert_eq!(&Value::int32_default(), to.get_type_info());
+        assert_eq!(None, to.get_data());
+    }
+
+    #[test]
+    fn test_pipeline_null_targets_fails_row_without_targets() {
+        let mut c = test_row(&[("range", 0, Some("3-7"))]);
+        let outcome = Pipeline::new()
+            .with_step(
+                "move nothing",
+                MutateItemIdx::new(ItemSelector::from("nothing"), 3),
+                StepErrorPolicy::NullTargets,
+            )
+            .apply_row(&mut c);
+        assert_eq!("move nothing", outcome.failure().unwrap().0);
+    }
+
+    #[test]
+    fn test_pipeline_null_targets_doesnt_rerun_op_without_targets() {
+        let mut c = test_row(&[("range", 0, Some("nope"))]);
+        let cast = CastItem {
+            idx: 0,
+            type_info: Value::int32_default(),
+            mode: CastMode::Strict,
+            conversion: ConversionOptions::default(),
+        };
+        let cast_only = Pipeline::new().with_step("cast", cast, StepErrorPolicy::NullTargets);
+        assert!(!cast_only.has_targets());
+        let outcome = cast_only.apply_row(&mut c);
+        assert_eq!(1, outcome.steps.len());
+        assert!(matches!(outcome.steps[0].status, StepStatus::RowFailed(_)));
+        assert_eq!(
+            &Value::from(String::from("nope")),
+            c.get_by_idx(0).unwrap().get_data().unwrap()
+        );
+        assert!(pipeline(StepErrorPolicy::FailRow).has_targets());
+    }
+}
@@ -0,0 +1,202 @@
+use venum::venum::Value;
+use venum_tds::{cell::DataCell, row::DataCellRow, traits::VDataContainerItem};
+
+use crate::value_conversion::type_name;
+
+/// A schema (items without data) of `String` items, one per name with the idx of its position,
+/// e.g. from the header of a CSV file.
+pub fn schema_from_names<S: AsRef<str>>(names: &[S]) -> DataCellRow {
+    let mut schema = DataCellRow::new();
+    for (idx, name) in names.iter().enumerate() {
+        schema.0.push(DataCell::new_without_data(
+            Value::string_default(),
+            String::from(name.as_ref()),
+            idx,
+        ));
+    }
+    schema
+}
+
+/// The schema of `row`, i.e. its items without data.
+pub fn schema_of(row: &DataCellRow) -> DataCellRow {
+    DataCellRow(
+        row.0
+            .iter()
+            .map(|dc| DataCell::new_without_data(dc.type_info.clone(), dc.name.clone(), dc.idx))
+            .collect(),
+    )
+}
+
+/// One line `idx name type` per item, in ascending order of idx.
+pub fn format_schema(schema: &DataCellRow) -> String {
+    let mut items: Vec<&DataCell> = schema.0.iter().collect();
+    items.sort_by_key(|dc| dc.get_idx());
+    let name_width = items.iter().map(|dc| dc.name.len()).max().unwrap_or(0);
+    items
+        .iter()
+        .map(|dc| {
+            format!(
+                "{:>3} {:<w$} {}\n",
+                dc.idx,
+                dc.name,
+                type_name(&dc.type_info),
+                w = name_width
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::{ConfigFormat, PipelineConfig},
+        container::{CastItem, DeleteItemAtIdx, OnConflict, SplitItemAtIdx, TargetConflict},
+        errors::{ContainerOpsErrors, VenumTdsTransRichError},
+        pipeline::StepStatus,
+        traits::{container::TransrichContainerInplace, item::CastMode},
+        value_conversion::ConversionOptions,
+        value_splitting::ValueStringSeparatorCharSplit,
+    };
+
+    use super::*;
+
+    fn split_op(
+        idx: usize,
+        target_left_idx: usize,
+    ) -> OnConflict<SplitItemAtIdx<ValueStringSeparatorCharSplit>> {
+        OnConflict {
+            op: SplitItemAtIdx {
+                idx,
+                divider: ValueStringSeparatorCharSplit {
+                    sep_char: '-',
+                    split_none: true,
+                },
+                target_left: (
+                    Value::int32_default(),
+                    target_left_idx,
+                    String::from("from"),
+                ),
+                target_right: (Value::int32_default(), 10, String::from("to")),
+                delete_source_item: true,
+            },
+            on_conflict: TargetConflict::Fail,
+        }
+    }
+
+    #[test]
+    fn test_schema_of_row() {
+        let mut row = schema_from_names(&["a", "b"]);
+        row.0[1].data = Some(Value::String(String::from("x")));
+        let schema = schema_of(&row);
+        assert_eq!(schema_from_names(&["a", "b"]), schema);
+        assert!(schema.0.iter().all(|dc| dc.data.is_none()));
+    }
+
+    #[test]
+    fn test_split_schema() {
+        let mut schema = schema_from_names(&["range", "qty"]);
+        split_op(0, 5).apply_schema(&mut schema).unwrap();
+        assert_eq!(
+            "  1 qty  string\n  5 from i32\n 10 to   i32\n",
+            format_schema(&schema)
+        );
+    }
+
+    #[test]
+    fn test_split_schema_errors() {
+        let mut schema = schema_from_names(&["range", "qty"]);
+        assert!(matches!(
+            split_op(0, 1).apply_schema(&mut schema),
+            Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::ItemConflict { idx: 1, .. }
+            ))
+        ));
+
+        schema.0[0].type_info = Value::bool_default();
+        assert_eq!(
+            Err(VenumTdsTransRichError::ContainerOps(
+                ContainerOpsErrors::DivideItemError {
+                    idx: 0,
+                    msg: String::from("Splitter can't split items of type bool"),
+                }
+            )),
+            split_op(0, 5).apply_schema(&mut schema)
+        );
+
+        assert!(DeleteItemAtIdx(2).apply_schema(&mut schema).is_err());
+        assert!(split_op(2, 5).apply_schema(&mut schema).is_err());
+    }
+
+    #[test]
+    fn test_cast_schema() {
+        let mut schema = schema_from_names(&["paid", "due"]);
+        let cast = |idx: usize, type_info: Value| CastItem {
+            idx,
+            type_info,
+            mode: CastMode::Strict,
+            conversion: ConversionOptions::default(),
+        };
+        cast(0, Value::bool_default())
+            .apply_schema(&mut schema)
+            .unwrap();
+        cast(1, Value::naive_date_default())
+            .apply_schema(&mut schema)
+            .unwrap();
+        assert!(matches!(
+            cast(0, Value::naive_date_default()).apply_schema(&mut schema),
+            Err(VenumTdsTransRichError::Target { idx: 0, .. })
+        ));
+        assert_eq!("  0 paid bool\n  1 due  date\n", format_schema(&schema));
+    }
+
+    #[test]
+    fn test_check_pipeline_against_header() {
+        let config = r#"
+steps:
+  - op:
+      split:
+        item: range
+        splitter:
+          separator_char: { sep_char: "-" }
+        left: { type: i32, idx: 2, name: from }
+        right: { type: i32, idx: 3, name: to }
+        delete_source_item: true
+  - op:
+      merge:
+        a: from
+        b: to
+        merger: { join: { separator: ".." } }
+        target: { type: string, idx: 4, name: range }
+  - op:
+      split:
+        item: to
+        splitter:
+          separator_char: { sep_char: "-" }
+        left: { type: i32, idx: 5, name: a }
+        right: { type: i32, idx: 6, name: b }
+"#;
+        let pipeline = PipelineConfig::parse(config, ConfigFormat::Yaml)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut schema = schema_from_names(&["range", "qty"]);
+        let outcome = pipeline.check_schema(&mut schema);
+        assert!(matches!(outcome.steps[0].status, StepStatus::Applied));
+        assert!(matches!(outcome.steps[1].status, StepStatus::Applied));
+        assert_eq!(
+            Some((
+                "step 2",
+                &VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::DivideItemError {
+                    idx: 3,
+                    msg: String::from("Splitter can't split items of type i32"),
+                })
+            )),
+            outcome.failure()
+        );
+        assert_eq!(
+            "  1 qty   string\n  2 from  i32\n  3 to    i32\n  4 range string\n",
+            format_schema(&schema)
+        );
+    }
+}
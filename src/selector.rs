@@ -0,0 +1,244 @@
+use std::ops::Range;
+
+use regex::Regex;
+use venum::venum::Value;
+use venum_tds::traits::{VDataContainer, VDataContainerItem};
+
+use crate::{
+    errors::{ContainerOpsErrors, Result, VenumTdsTransRichError},
+    traits::container::TransrichContainerItems,
+};
+
+/// How a container op finds the item(s) it works on: a plain idx works with any `VDataContainer`
+/// and is taken as is (a missing item is reported by the op), an `ItemSelector` needs a container
+/// that lists its items.
+pub trait SelectItems<C: VDataContainer> {
+    /// The idx of all selected items, in ascending order.
+    fn resolve(&self, container: &C) -> Result<Vec<usize>>;
+
+    /// The idx of the one selected item.
+    fn resolve_one(&self, container: &C) -> Result<usize>;
+}
+
+impl<C: VDataContainer> SelectItems<C> for usize {
+    fn resolve(&self, _container: &C) -> Result<Vec<usize>> {
+        Ok(vec![*self])
+    }
+
+    fn resolve_one(&self, _container: &C) -> Result<usize> {
+        Ok(*self)
+    }
+}
+
+impl<C: TransrichContainerItems> SelectItems<C> for ItemSelector {
+    fn resolve(&self, container: &C) -> Result<Vec<usize>> {
+        ItemSelector::resolve(self, container)
+    }
+
+    fn resolve_one(&self, container: &C) -> Result<usize> {
+        ItemSelector::resolve_one(self, container)
+    }
+}
+
+/// Selects the item(s) of a container an operation works on.
+#[derive(Debug, Clone)]
+pub enum ItemSelector {
+    Idx(usize),
+    /// All items with an idx in the (half-open) range.
+    IdxRange(Range<usize>),
+    Name(String),
+    NameIgnoreCase(String),
+    NameRegex(Regex),
+    /// All items whose type_info is of the same variant as the given one.
+    TypeInfo(Value),
+}
+
+impl ItemSelector {
+    pub fn name_regex(regex_pattern: String) -> Result<Self> {
+        let re = Regex::new(regex_pattern.as_str()).map_err(|e| {
+            VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::SelectorError {
+                selector: format!("NameRegex({})", regex_pattern),
+                msg: format!("{} (ItemSelector, ERROR_ON_REGEX_COMPILE)", e),
+            })
+        })?;
+        Ok(ItemSelector::NameRegex(re))
+    }
+
+    pub fn matches<ENTRY: VDataContainerItem>(&self, item: &ENTRY) -> bool {
+        match self {
+            ItemSelector::Idx(idx) => item.get_idx() == *idx,
+            ItemSelector::IdxRange(range) => range.contains(&item.get_idx()),
+            ItemSelector::Name(name) => item.get_name() == name,
+            ItemSelector::NameIgnoreCase(name) => {
+                item.get_name().to_lowercase() == name.to_lowercase()
+            }
+            ItemSelector::NameRegex(re) => re.is_match(item.get_name()),
+            ItemSelector::TypeInfo(type_info) => {
+                std::mem::discriminant(item.get_type_info()) == std::mem::discriminant(type_info)
+            }
+        }
+    }
+
+    /// The idx of all matching items, in ascending order. Fails, if nothing matches.
+    pub fn resolve<CONT: TransrichContainerItems>(&self, container: &CONT) -> Result<Vec<usize>> {
+        let mut idxs: Vec<usize> = container
+            .items()
+            .iter()
+            .filter(|item| self.matches(*item))
+            .map(|item| item.get_idx())
+            .collect();
+        if idxs.is_empty() {
+            return Err(self.err(String::from("matched no item")));
+        }
+        idxs.sort_unstable();
+        Ok(idxs)
+    }
+
+    /// The idx of the one matching item. Fails, if nothing or more than one item matches.
+    pub fn resolve_one<CONT: TransrichContainerItems>(&self, container: &CONT) -> Result<usize> {
+        match self.resolve(container)?.as_slice() {
+            [idx] => Ok(*idx),
+            idxs => Err(self.err(format!(
+                "matched {} items (at idx {:?}), but exactly one is required",
+                idxs.len(),
+                idxs
+            ))),
+        }
+    }
+
+    fn err(&self, msg: String) -> VenumTdsTransRichError {
+        VenumTdsTransRichError::ContainerOps(ContainerOpsErrors::SelectorError {
+            selector: format!("{}", self),
+            msg,
+        })
+    }
+}
+
+impl std::fmt::Display for ItemSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemSelector::NameRegex(re) => write!(f, "NameRegex({})", re.as_str()),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// Regexes are equal, if their patterns are.
+impl PartialEq for ItemSelector {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ItemSelector::Idx(a), ItemSelector::Idx(b)) => a == b,
+            (ItemSelector::IdxRange(a), ItemSelector::IdxRange(b)) => a == b,
+            (ItemSelector::Name(a), ItemSelector::Name(b)) => a == b,
+            (ItemSelector::NameIgnoreCase(a), ItemSelector::NameIgnoreCase(b)) => a == b,
+            (ItemSelector::NameRegex(a), ItemSelector::NameRegex(b)) => a.as_str() == b.as_str(),
+            (ItemSelector::TypeInfo(a), ItemSelector::TypeInfo(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl From<usize> for ItemSelector {
+    fn from(idx: usize) -> Self {
+        ItemSelector::Idx(idx)
+    }
+}
+
+impl From<&str> for ItemSelector {
+    fn from(name: &str) -> Self {
+        ItemSelector::Name(String::from(name))
+    }
+}
+
+impl From<Range<usize>> for ItemSelector {
+    fn from(range: Range<usize>) -> Self {
+        ItemSelector::IdxRange(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use venum_tds::row::DataCellRow;
+
+    use crate::test_util::test_row;
+
+    use super::*;
+
+    fn row() -> DataCellRow {
+        let mut c = test_row(&[("Street", 0, None), ("zip", 2, None), ("city", 1, None)]);
+        c.0[1].type_info = Value::int32_default();
+        c
+    }
+
+    #[test]
+    fn test_resolve_selectors() {
+        let c = row();
+        assert_eq!(vec![2], ItemSelector::Idx(2).resolve(&c).unwrap());
+        assert_eq!(vec![1, 2], ItemSelector::from(1..5).resolve(&c).unwrap());
+        assert_eq!(2, ItemSelector::from("zip").resolve_one(&c).unwrap());
+        assert_eq!(
+            0,
+            ItemSelector::NameIgnoreCase(String::from("STREET"))
+                .resolve_one(&c)
+                .unwrap()
+        );
+        assert_eq!(
+            vec![1, 2],
+            ItemSelector::name_regex(String::from("^(zip|city)$"))
+                .unwrap()
+                .resolve(&c)
+                .unwrap()
+        );
+        assert_eq!(
+            vec![0, 1],
+            ItemSelector::TypeInfo(Value::string_default())
+                .resolve(&c)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ContainerOps(SelectorError { selector: \"Name(\\\"street\\\")\", msg: \"matched no item\" })"
+    )]
+    fn test_resolve_err_no_match() {
+        ItemSelector::from("street").resolve(&row()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ContainerOps(SelectorError { selector: \"Idx(7)\", msg: \"matched no item\" })"
+    )]
+    fn test_resolve_err_no_item_at_idx() {
+        ItemSelector::Idx(7).resolve(&row()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ContainerOps(SelectorError { selector: \"NameRegex((?i)^[sc])\", msg: \"matched 2 items (at idx [0, 1]), but exactly one is required\" })"
+    )]
+    fn test_resolve_one_err_too_many() {
+        ItemSelector::name_regex(String::from("(?i)^[sc]"))
+            .unwrap()
+            .resolve_one(&row())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_selector_eq() {
+        assert_eq!(ItemSelector::Idx(1), ItemSelector::from(1));
+        assert_ne!(ItemSelector::Idx(1), ItemSelector::from(1..2));
+        assert_ne!(
+            ItemSelector::Name(String::from("zip")),
+            ItemSelector::NameIgnoreCase(String::from("zip"))
+        );
+        assert_eq!(
+            ItemSelector::name_regex(String::from("^zip$")).unwrap(),
+            ItemSelector::name_regex(String::from("^zip$")).unwrap()
+        );
+        assert_ne!(
+            ItemSelector::name_regex(String::from("^zip$")).unwrap(),
+            ItemSelector::Name(String::from("^zip$"))
+        );
+    }
+}
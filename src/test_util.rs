@@ -0,0 +1,16 @@
+use venum::venum::Value;
+use venum_tds::{cell::DataCell, row::DataCellRow};
+
+/// A row of `String` items, one per `(name, idx, data)`, in the given order.
+pub(crate) fn test_row(cells: &[(&str, usize, Option<&str>)]) -> DataCellRow {
+    let mut c = DataCellRow::new();
+    for (name, idx, data) in cells {
+        c.0.push(DataCell::new(
+            Value::string_default(),
+            String::from(*name),
+            *idx,
+            data.map(|d| Value::from(String::from(d))),
+        ));
+    }
+    c
+}
@@ -4,4 +4,65 @@ use crate::errors::Result;
 
 pub trait TransrichContainerInplace<C: VDataContainer> {
     fn apply(&self, container: &mut C) -> Result<()>;
+
+    /// Applies the op to a schema, i.e. a container of items without data, to check the op (e.g.
+    /// for missing sources, target conflicts or source types that can't be handled) before any
+    /// data is processed. The result is the schema of the output. The default is `apply`, which is
+    /// right for ops that only change the structure.
+    fn apply_schema(&self, schema: &mut C) -> Result<()> {
+        self.apply(schema)
+    }
+
+    /// Whether the op adds targets, that `pipeline::StepErrorPolicy::NullTargets` can add without
+    /// data if the op fails. The default is `false`, for ops that only change existing items.
+    fn has_targets(&self) -> bool {
+        false
+    }
+}
+
+impl<C: VDataContainer, T: TransrichContainerInplace<C> + ?Sized> TransrichContainerInplace<C>
+    for Box<T>
+{
+    fn apply(&self, container: &mut C) -> Result<()> {
+        (**self).apply(container)
+    }
+
+    fn apply_schema(&self, schema: &mut C) -> Result<()> {
+        (**self).apply_schema(schema)
+    }
+
+    fn has_targets(&self) -> bool {
+        (**self).has_targets()
+    }
+}
+
+/// The items an op adds to a container and the idx of the items they replace, e.g. the source of
+/// a split, that is deleted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewItems<ITEM> {
+    pub items: Vec<ITEM>,
+    pub replaced: Vec<usize>,
+}
+
+/// An op that adds new items to a container. `produce` creates them without modifying the
+/// container, so they can be checked for conflicts with the existing items before they are added
+/// (see `container::OnConflict`).
+pub trait TransrichItemsProducer<C: VDataContainer> {
+    fn produce(&self, container: &C) -> Result<NewItems<C::ITEM>>;
+
+    /// Like `produce`, for a schema (see `TransrichContainerInplace::apply_schema`).
+    fn produce_schema(&self, schema: &C) -> Result<NewItems<C::ITEM>> {
+        self.produce(schema)
+    }
+}
+
+/// Access to all items of a container, which `VDataContainer` itself doesn't offer.
+///
+/// It's needed to resolve an `ItemSelector`, to check new items for idx and name conflicts
+/// (`container::OnConflict`) and by the ops that renumber items (e.g. `MoveItem`). A container
+/// other than `DataCellRow` has to implement it (in addition to `VDataContainer`) to be used with
+/// them. With a plain idx, the other ops work on any `VDataContainer`.
+pub trait TransrichContainerItems: VDataContainer {
+    fn items(&self) -> &[Self::ITEM];
+    fn items_mut(&mut self) -> &mut [Self::ITEM];
 }
@@ -1,6 +1,12 @@
-use crate::errors::Result;
+use crate::{
+    errors::Result,
+    value_conversion::{ConversionOptions, TargetConversions},
+};
 
-use super::value::Split;
+use serde::Deserialize;
+use venum::venum::Value;
+
+use super::value::{Merge, MergeN, Split, SplitKeyed, SplitN};
 
 pub trait SplitUsing<D: Split> {
     type ITEM;
@@ -10,5 +16,194 @@ pub trait SplitUsing<D: Split> {
         split_impl: &D,
         dst_left: &mut Self::ITEM,
         dst_right: &mut Self::ITEM,
+    ) -> Result<()> {
+        self.split_using_with(
+            split_impl,
+            dst_left,
+            dst_right,
+            &TargetConversions::default(),
+        )
+    }
+
+    /// Like `split_using`, but the targets are converted according to `conversions`.
+    fn split_using_with(
+        &self,
+        split_impl: &D,
+        dst_left: &mut Self::ITEM,
+        dst_right: &mut Self::ITEM,
+        conversions: &TargetConversions,
+    ) -> Result<()>;
+}
+
+/// What to do, if the number of tokens of an N-way split differs from the number of targets.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitNTargetMismatch {
+    /// Any mismatch is an error.
+    #[default]
+    Fail,
+    /// Fewer tokens than targets: the remaining targets are set to `None`. More tokens are an error.
+    PadWithNone,
+    /// More tokens than targets: the tail is joined (with the given separator) into the last target.
+    /// Fewer tokens are an error.
+    FoldTailIntoLast(String),
+}
+
+pub trait SplitNUsing<D: SplitN> {
+    type ITEM;
+
+    fn split_n_using(
+        &self,
+        split_impl: &D,
+        dsts: &mut [Self::ITEM],
+        on_mismatch: &SplitNTargetMismatch,
+    ) -> Result<()> {
+        self.split_n_using_with(split_impl, dsts, on_mismatch, &TargetConversions::default())
+    }
+
+    /// Like `split_n_using`, but the targets are converted according to `conversions`.
+    fn split_n_using_with(
+        &self,
+        split_impl: &D,
+        dsts: &mut [Self::ITEM],
+        on_mismatch: &SplitNTargetMismatch,
+        conversions: &TargetConversions,
+    ) -> Result<()>;
+}
+
+/// What to do with (key, value) pairs whose key doesn't belong to any target. `T` is the item the
+/// unknown pairs are collected into, e.g. a target descriptor of a container op or the item itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnknownKeys<T> {
+    Ignore,
+    Fail,
+    /// Re-join the unknown pairs and put them into the given item.
+    CollectInto(T),
+}
+
+impl<T> UnknownKeys<T> {
+    pub fn as_ref(&self) -> UnknownKeys<&T> {
+        match self {
+            UnknownKeys::Ignore => UnknownKeys::Ignore,
+            UnknownKeys::Fail => UnknownKeys::Fail,
+            UnknownKeys::CollectInto(t) => UnknownKeys::CollectInto(t),
+        }
+    }
+
+    pub fn as_mut(&mut self) -> UnknownKeys<&mut T> {
+        match self {
+            UnknownKeys::Ignore => UnknownKeys::Ignore,
+            UnknownKeys::Fail => UnknownKeys::Fail,
+            UnknownKeys::CollectInto(t) => UnknownKeys::CollectInto(t),
+        }
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> UnknownKeys<U> {
+        match self {
+            UnknownKeys::Ignore => UnknownKeys::Ignore,
+            UnknownKeys::Fail => UnknownKeys::Fail,
+            UnknownKeys::CollectInto(t) => UnknownKeys::CollectInto(f(t)),
+        }
+    }
+
+    /// The item the unknown pairs are collected into, if any.
+    pub fn collect_into(self) -> Option<T> {
+        match self {
+            UnknownKeys::CollectInto(t) => Some(t),
+            _ => None,
+        }
+    }
+}
+
+pub trait SplitKeyedUsing<D: SplitKeyed> {
+    type ITEM;
+
+    /// `dsts` are (key, default, target) triples. The default is used if the key is missing.
+    fn split_keyed_using(
+        &self,
+        split_impl: &D,
+        dsts: &mut [(String, Option<Value>, Self::ITEM)],
+        unknown_keys: UnknownKeys<&mut Self::ITEM>,
+    ) -> Result<()> {
+        self.split_keyed_using_with(
+            split_impl,
+            dsts,
+            unknown_keys,
+            &TargetConversions::default(),
+        )
+    }
+
+    /// Like `split_keyed_using`, but the targets are converted according to `conversions`.
+    fn split_keyed_using_with(
+        &self,
+        split_impl: &D,
+        dsts: &mut [(String, Option<Value>, Self::ITEM)],
+        unknown_keys: UnknownKeys<&mut Self::ITEM>,
+        conversions: &TargetConversions,
+    ) -> Result<()>;
+}
+
+pub trait MergeUsing<M: Merge> {
+    type ITEM;
+
+    fn merge_using(
+        &self,
+        merge_impl: &M,
+        src_other: &Self::ITEM,
+        dst: &mut Self::ITEM,
+    ) -> Result<()> {
+        self.merge_using_with(merge_impl, src_other, dst, &TargetConversions::default())
+    }
+
+    /// Like `merge_using`, but the target is converted according to `conversions`.
+    fn merge_using_with(
+        &self,
+        merge_impl: &M,
+        src_other: &Self::ITEM,
+        dst: &mut Self::ITEM,
+        conversions: &TargetConversions,
+    ) -> Result<()>;
+}
+
+pub trait MergeNUsing<M: MergeN> {
+    type ITEM;
+
+    fn merge_n_using(
+        &self,
+        merge_impl: &M,
+        src_others: Vec<&Self::ITEM>,
+        dst: &mut Self::ITEM,
+    ) -> Result<()> {
+        self.merge_n_using_with(merge_impl, src_others, dst, &TargetConversions::default())
+    }
+
+    /// Like `merge_n_using`, but the target is converted according to `conversions`.
+    fn merge_n_using_with(
+        &self,
+        merge_impl: &M,
+        src_others: Vec<&Self::ITEM>,
+        dst: &mut Self::ITEM,
+        conversions: &TargetConversions,
+    ) -> Result<()>;
+}
+
+/// How a failing conversion of the existing data is handled when casting an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CastMode {
+    /// Fail and leave the item untouched.
+    #[default]
+    Strict,
+    /// Cast anyway and set the data to `None`.
+    Lenient,
+}
+
+pub trait CastTo {
+    /// Changes the type_info of the item to `type_info` and converts its data accordingly.
+    fn cast_to(
+        &mut self,
+        type_info: &Value,
+        mode: CastMode,
+        conversion: &ConversionOptions,
     ) -> Result<()>;
 }
@@ -4,10 +4,38 @@ use crate::errors::Result;
 
 pub trait Split {
     fn split(&self, src: &Option<Value>) -> Result<(Option<Value>, Option<Value>)>;
+
+    /// Whether items of the variant of `type_info` can be split at all, e.g. to check a schema.
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        matches!(type_info, Value::String(_))
+    }
 }
 
 pub trait SplitN {
     fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>>;
+
+    /// Whether items of the variant of `type_info` can be split at all, e.g. to check a schema.
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        matches!(type_info, Value::String(_))
+    }
+}
+
+/// A `SplitN` whose tokens (can) have names, e.g. named capture groups of a regex.
+/// `token_names()[i]` is the name of the i-th token returned by `split_n`.
+pub trait SplitNNamed: SplitN {
+    fn token_names(&self) -> Vec<Option<String>>;
+}
+
+/// Splits a value into (key, value) pairs, e.g. `color=red;size=XL`.
+pub trait SplitKeyed {
+    fn split_keyed(&self, src: &Option<Value>) -> Result<Vec<(String, Option<Value>)>>;
+    /// The inverse of `split_keyed`.
+    fn join_keyed(&self, pairs: &[(String, Option<Value>)]) -> Value;
+
+    /// Whether items of the variant of `type_info` can be split at all, e.g. to check a schema.
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        matches!(type_info, Value::String(_))
+    }
 }
 
 // concat | join | template
@@ -21,5 +49,59 @@ pub trait Merge {
 }
 
 pub trait MergeN {
-    fn merge(&self, src: Vec<&Option<Value>>) -> Result<Option<Value>>;
+    fn merge_n(&self, src: Vec<&Option<Value>>) -> Result<Option<Value>>;
+}
+
+// Boxed (trait object) splitters and mergers, e.g. built from a config, can be used like the
+// concrete ones.
+impl<S: Split + ?Sized> Split for Box<S> {
+    fn split(&self, src: &Option<Value>) -> Result<(Option<Value>, Option<Value>)> {
+        (**self).split(src)
+    }
+
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        (**self).accepts_type(type_info)
+    }
+}
+
+impl<S: SplitN + ?Sized> SplitN for Box<S> {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        (**self).split_n(src)
+    }
+
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        (**self).accepts_type(type_info)
+    }
+}
+
+impl<S: SplitNNamed + ?Sized> SplitNNamed for Box<S> {
+    fn token_names(&self) -> Vec<Option<String>> {
+        (**self).token_names()
+    }
+}
+
+impl<S: SplitKeyed + ?Sized> SplitKeyed for Box<S> {
+    fn split_keyed(&self, src: &Option<Value>) -> Result<Vec<(String, Option<Value>)>> {
+        (**self).split_keyed(src)
+    }
+
+    fn join_keyed(&self, pairs: &[(String, Option<Value>)]) -> Value {
+        (**self).join_keyed(pairs)
+    }
+
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        (**self).accepts_type(type_info)
+    }
+}
+
+impl<M: Merge + ?Sized> Merge for Box<M> {
+    fn merge(&self, src_a: &Option<Value>, src_b: &Option<Value>) -> Result<Option<Value>> {
+        (**self).merge(src_a, src_b)
+    }
+}
+
+impl<M: MergeN + ?Sized> MergeN for Box<M> {
+    fn merge_n(&self, src: Vec<&Option<Value>>) -> Result<Option<Value>> {
+        (**self).merge_n(src)
+    }
 }
@@ -0,0 +1,796 @@
+//! Conversion of a `Value` into the variant of a target type, used wherever a split/merge result
+//! has to be put into a typed destination.
+//!
+//! The supported conversions are:
+//!
+//! | from \ to                  | conversion                                                            |
+//! |----------------------------|-----------------------------------------------------------------------|
+//! | same variant               | cloned as is                                                          |
+//! | any                        | `String`, see [`value_to_string`]                                     |
+//! | `String`, `Char`           | parsed via `Value::from_string_with_templ`, see [`ConversionOptions`] |
+//! | any                        | `Char`, if the stringified value is exactly one character             |
+//! | integers, `Bool`           | integers (overflow checked), floats, `Decimal` (range checked)        |
+//! | integers                   | `Bool`, if the value is `0` or `1`                                    |
+//! | floats, `Decimal`          | integers, if the value has no fractional part and is in range         |
+//! | floats, `Decimal`          | floats (range checked), `Decimal` (range checked)                     |
+//! | `NaiveDate`                | `NaiveDateTime`/`DateTime` at midnight (UTC for `DateTime`)           |
+//! | `NaiveDateTime`            | `NaiveDate` (time dropped), `DateTime` (as UTC)                       |
+//! | `DateTime`                 | `NaiveDate`/`NaiveDateTime` (local date/time, offset dropped)         |
+//!
+//! `Bool` is treated as `0`/`1`. Everything else is an error.
+
+use std::borrow::Cow;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use serde::Deserialize;
+use venum::venum::Value;
+
+use crate::errors::{ConversionError, Result, VenumTdsTransRichError};
+
+/// Stringifies any `Value` variant, so that it can be merged into a `Value::String`.
+pub fn value_to_string(val: &Value) -> String {
+    match val {
+        Value::Char(c) => c.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Int8(v) => v.to_string(),
+        Value::Int16(v) => v.to_string(),
+        Value::Int32(v) => v.to_string(),
+        Value::Int64(v) => v.to_string(),
+        Value::Int128(v) => v.to_string(),
+        Value::UInt8(v) => v.to_string(),
+        Value::UInt16(v) => v.to_string(),
+        Value::UInt32(v) => v.to_string(),
+        Value::UInt64(v) => v.to_string(),
+        Value::UInt128(v) => v.to_string(),
+        Value::Float32(v) => v.to_string(),
+        Value::Float64(v) => v.to_string(),
+        Value::Bool(v) => v.to_string(),
+        Value::Decimal(v) => v.to_string(),
+        Value::NaiveDate(v) => v.to_string(),
+        Value::NaiveDateTime(v) => v.to_string(),
+        Value::DateTime(v) => v.to_rfc3339(),
+    }
+}
+
+/// The name of the variant, as used in error messages.
+pub fn variant_name(val: &Value) -> &'static str {
+    match val {
+        Value::Char(_) => "Char",
+        Value::String(_) => "String",
+        Value::Int8(_) => "Int8",
+        Value::Int16(_) => "Int16",
+        Value::Int32(_) => "Int32",
+        Value::Int64(_) => "Int64",
+        Value::Int128(_) => "Int128",
+        Value::UInt8(_) => "UInt8",
+        Value::UInt16(_) => "UInt16",
+        Value::UInt32(_) => "UInt32",
+        Value::UInt64(_) => "UInt64",
+        Value::UInt128(_) => "UInt128",
+        Value::Float32(_) => "Float32",
+        Value::Float64(_) => "Float64",
+        Value::Bool(_) => "Bool",
+        Value::Decimal(_) => "Decimal",
+        Value::NaiveDate(_) => "NaiveDate",
+        Value::NaiveDateTime(_) => "NaiveDateTime",
+        Value::DateTime(_) => "DateTime",
+    }
+}
+
+/// The name of the type, as used in type strings of configs and schemas, e.g. `i32`.
+pub fn type_name(type_info: &Value) -> &'static str {
+    match type_info {
+        Value::Char(_) => "char",
+        Value::String(_) => "string",
+        Value::Int8(_) => "i8",
+        Value::Int16(_) => "i16",
+        Value::Int32(_) => "i32",
+        Value::Int64(_) => "i64",
+        Value::Int128(_) => "i128",
+        Value::UInt8(_) => "u8",
+        Value::UInt16(_) => "u16",
+        Value::UInt32(_) => "u32",
+        Value::UInt64(_) => "u64",
+        Value::UInt128(_) => "u128",
+        Value::Float32(_) => "f32",
+        Value::Float64(_) => "f64",
+        Value::Bool(_) => "bool",
+        Value::Decimal(_) => "decimal",
+        Value::NaiveDate(_) => "date",
+        Value::NaiveDateTime(_) => "datetime",
+        Value::DateTime(_) => "datetime_tz",
+    }
+}
+
+const UNSUPPORTED: &str = "unsupported conversion";
+const OUT_OF_RANGE: &str = "value out of range";
+const FRACTIONAL: &str = "value has a fractional part";
+const NOT_FINITE: &str = "value is not a finite number";
+
+/// Decimal and thousands separator of numbers in strings, e.g. `1.234,5` is
+/// `NumberLocale { decimal_sep: ',', thousands_sep: Some('.') }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NumberLocale {
+    pub decimal_sep: char,
+    pub thousands_sep: Option<char>,
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        NumberLocale {
+            decimal_sep: '.',
+            thousands_sep: None,
+        }
+    }
+}
+
+/// Options for parsing strings into typed targets. They only apply to `String` sources, the
+/// defaults leave parsing to `Value::from_string_with_templ`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConversionOptions {
+    /// Applies to integer, float and `Decimal` targets.
+    pub number_locale: NumberLocale,
+    /// `chrono` formats for `NaiveDate` targets, tried in order before the default format.
+    pub date_formats: Vec<String>,
+    /// `chrono` formats for `NaiveDateTime` and `DateTime` targets, tried in order before the
+    /// default format.
+    pub date_time_formats: Vec<String>,
+    /// Strings (compared case-insensitively) for `true`/`false` of `Bool` targets, tried before
+    /// the default parsing.
+    pub truthy: Vec<String>,
+    pub falsy: Vec<String>,
+    /// Prefixes/suffixes like currency symbols or `%` to remove (together with surrounding
+    /// whitespace) before parsing.
+    pub strip_affixes: Vec<String>,
+}
+
+static DEFAULT_OPTIONS: ConversionOptions = ConversionOptions {
+    number_locale: NumberLocale {
+        decimal_sep: '.',
+        thousands_sep: None,
+    },
+    date_formats: Vec::new(),
+    date_time_formats: Vec::new(),
+    truthy: Vec::new(),
+    falsy: Vec::new(),
+    strip_affixes: Vec::new(),
+};
+
+impl ConversionOptions {
+    fn strip<'a>(&self, s: &'a str) -> &'a str {
+        if self.strip_affixes.is_empty() {
+            return s;
+        }
+        let mut stripped = s.trim();
+        loop {
+            let len_before = stripped.len();
+            for affix in self.strip_affixes.iter().filter(|a| !a.is_empty()) {
+                if let Some(rest) = stripped.strip_prefix(affix.as_str()) {
+                    stripped = rest.trim_start();
+                }
+                if let Some(rest) = stripped.strip_suffix(affix.as_str()) {
+                    stripped = rest.trim_end();
+                }
+            }
+            if stripped.len() == len_before {
+                return stripped;
+            }
+        }
+    }
+
+    fn normalize_number<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        if self.number_locale == NumberLocale::default() {
+            return Cow::Borrowed(s);
+        }
+        Cow::Owned(
+            s.chars()
+                .filter(|c| Some(*c) != self.number_locale.thousands_sep)
+                .map(|c| match c {
+                    _ if c == self.number_locale.decimal_sep => '.',
+                    _ => c,
+                })
+                .collect(),
+        )
+    }
+
+    /// `None` if there are no custom formats/sets for the target type, or none of them matched.
+    fn parse_custom(&self, s: &str, type_info: &Value) -> Option<Value> {
+        match type_info {
+            Value::Bool(_) => {
+                let is_in =
+                    |set: &[String]| set.iter().any(|t| t.to_lowercase() == s.to_lowercase());
+                if is_in(&self.truthy) {
+                    Some(Value::Bool(true))
+                } else if is_in(&self.falsy) {
+                    Some(Value::Bool(false))
+                } else {
+                    None
+                }
+            }
+            Value::NaiveDate(_) => self
+                .date_formats
+                .iter()
+                .find_map(|f| NaiveDate::parse_from_str(s, f).ok())
+                .map(Value::NaiveDate),
+            Value::NaiveDateTime(_) => self
+                .date_time_formats
+                .iter()
+                .find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+                .map(Value::NaiveDateTime),
+            Value::DateTime(_) => self
+                .date_time_formats
+                .iter()
+                .find_map(|f| DateTime::parse_from_str(s, f).ok())
+                .map(Value::DateTime),
+            _ => None,
+        }
+    }
+
+    /// The custom formats/sets that were tried for the target type, for error messages.
+    fn custom_formats(&self, type_info: &Value) -> Option<String> {
+        let formats = match type_info {
+            Value::Bool(_) => {
+                return match self.truthy.is_empty() && self.falsy.is_empty() {
+                    true => None,
+                    false => Some(format!(
+                        "truthy: {:?}, falsy: {:?}",
+                        self.truthy, self.falsy
+                    )),
+                }
+            }
+            Value::NaiveDate(_) => &self.date_formats,
+            Value::NaiveDateTime(_) | Value::DateTime(_) => &self.date_time_formats,
+            _ => return None,
+        };
+        match formats.is_empty() {
+            true => None,
+            false => Some(format!("formats: {:?}", formats)),
+        }
+    }
+}
+
+/// Conversion options for (some of) the targets of an item split or merge, keyed by the idx of the
+/// target. Targets without options are converted with the default options. The container ops
+/// collect them from their targets (see `container::Target`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetConversions(pub Vec<(usize, ConversionOptions)>);
+
+impl TargetConversions {
+    pub fn for_idx(&self, idx: usize) -> &ConversionOptions {
+        self.0
+            .iter()
+            .find(|(i, _)| *i == idx)
+            .map(|(_, o)| o)
+            .unwrap_or(&DEFAULT_OPTIONS)
+    }
+}
+
+/// Converts `val` into the variant of `type_info` (only the variant of `type_info` matters, not
+/// its value). Returns `Ok(None)` only if parsing a string yields no value.
+pub fn convert(val: &Value, type_info: &Value) -> Result<Option<Value>> {
+    convert_with(val, type_info, &DEFAULT_OPTIONS)
+}
+
+/// Checks whether values of the variant of `type_info_from` can be converted into the variant of
+/// `type_info_to` at all, e.g. to check a schema. Whether a `String` or `Char` can be parsed
+/// depends on the value, so those always pass.
+pub fn check_convertible(type_info_from: &Value, type_info_to: &Value) -> Result<()> {
+    match type_info_from {
+        Value::String(_) | Value::Char(_) => Ok(()),
+        _ => convert(type_info_from, type_info_to).map(|_| ()),
+    }
+}
+
+/// Like [`convert`], but strings are parsed according to `options`.
+pub fn convert_with(
+    val: &Value,
+    type_info: &Value,
+    options: &ConversionOptions,
+) -> Result<Option<Value>> {
+    if std::mem::discriminant(val) == std::mem::discriminant(type_info) {
+        return Ok(Some(val.clone()));
+    }
+    match (val, type_info) {
+        (_, Value::String(_)) => Ok(Some(Value::String(value_to_string(val)))),
+        (Value::String(s), _) => parse_string(s, type_info, options),
+        (Value::Char(c), _) => Ok(Value::from_string_with_templ(&c.to_string(), type_info)?),
+        (_, Value::Char(_)) => Ok(Value::from_string_with_templ(
+            &value_to_string(val),
+            type_info,
+        )?),
+        _ => convert_non_textual(val, type_info)
+            .map(Some)
+            .map_err(|reason| {
+                VenumTdsTransRichError::Conversion(ConversionError::from(
+                    format!(
+                        "cannot convert {} into {}: {}",
+                        variant_name(val),
+                        variant_name(type_info),
+                        reason
+                    ),
+                    Some(val.clone()),
+                    None,
+                ))
+            }),
+    }
+}
+
+fn parse_string(s: &str, type_info: &Value, options: &ConversionOptions) -> Result<Option<Value>> {
+    let stripped = options.strip(s);
+    if let Some(val) = options.parse_custom(stripped, type_info) {
+        return Ok(Some(val));
+    }
+    let normalized = match is_integral_type(type_info)
+        || matches!(
+            type_info,
+            Value::Float32(_) | Value::Float64(_) | Value::Decimal(_)
+        ) {
+        true => options.normalize_number(stripped),
+        false => Cow::Borrowed(stripped),
+    };
+    match (
+        Value::from_string_with_templ(&normalized, type_info),
+        options.custom_formats(type_info),
+    ) {
+        (Ok(val), _) => Ok(val),
+        (Err(e), None) => Err(e.into()),
+        (Err(_), Some(formats)) => Err(VenumTdsTransRichError::Conversion(ConversionError::from(
+            format!(
+                "cannot parse '{}' into {}: matches neither the custom nor the default format",
+                stripped,
+                variant_name(type_info)
+            ),
+            Some(Value::String(String::from(s))),
+            Some(formats),
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Integral {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+impl Integral {
+    fn narrow<T: TryFrom<i128> + TryFrom<u128>>(self) -> std::result::Result<T, &'static str> {
+        match self {
+            Integral::Signed(v) => T::try_from(v).map_err(|_| OUT_OF_RANGE),
+            Integral::Unsigned(v) => T::try_from(v).map_err(|_| OUT_OF_RANGE),
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Integral::Signed(v) => v as f64,
+            Integral::Unsigned(v) => v as f64,
+        }
+    }
+
+    fn repr(self) -> String {
+        match self {
+            Integral::Signed(v) => v.to_string(),
+            Integral::Unsigned(v) => v.to_string(),
+        }
+    }
+}
+
+fn as_integral(val: &Value) -> Option<Integral> {
+    match val {
+        Value::Int8(v) => Some(Integral::Signed(i128::from(*v))),
+        Value::Int16(v) => Some(Integral::Signed(i128::from(*v))),
+        Value::Int32(v) => Some(Integral::Signed(i128::from(*v))),
+        Value::Int64(v) => Some(Integral::Signed(i128::from(*v))),
+        Value::Int128(v) => Some(Integral::Signed(*v)),
+        Value::UInt8(v) => Some(Integral::Unsigned(u128::from(*v))),
+        Value::UInt16(v) => Some(Integral::Unsigned(u128::from(*v))),
+        Value::UInt32(v) => Some(Integral::Unsigned(u128::from(*v))),
+        Value::UInt64(v) => Some(Integral::Unsigned(u128::from(*v))),
+        Value::UInt128(v) => Some(Integral::Unsigned(*v)),
+        Value::Bool(b) => Some(Integral::Unsigned(u128::from(*b))),
+        _ => None,
+    }
+}
+
+fn convert_non_textual(val: &Value, type_info: &Value) -> std::result::Result<Value, &'static str> {
+    if let Some(i) = as_integral(val) {
+        return integral_to(i, type_info);
+    }
+    match val {
+        Value::Float32(f) => float_to(f64::from(*f), f.to_string(), type_info),
+        Value::Float64(f) => float_to(*f, f.to_string(), type_info),
+        Value::Decimal(d) if matches!(type_info, Value::Float32(_) | Value::Float64(_)) => {
+            let repr = d.normalize().to_string();
+            match type_info {
+                Value::Float32(_) => repr.parse().map(Value::Float32),
+                _ => repr.parse().map(Value::Float64),
+            }
+            .map_err(|_| OUT_OF_RANGE)
+        }
+        Value::Decimal(d) if d.fract().is_zero() => {
+            let i = d
+                .normalize()
+                .to_string()
+                .parse::<i128>()
+                .map_err(|_| OUT_OF_RANGE)?;
+            match type_info {
+                Value::Bool(_) => Err(UNSUPPORTED),
+                _ => integral_to(Integral::Signed(i), type_info),
+            }
+        }
+        Value::Decimal(_) if is_integral_type(type_info) => Err(FRACTIONAL),
+        Value::NaiveDate(d) => match type_info {
+            Value::NaiveDateTime(_) => midnight(d).map(Value::NaiveDateTime),
+            Value::DateTime(_) => midnight(d).map(|dt| Value::DateTime(as_utc(&dt))),
+            _ => Err(UNSUPPORTED),
+        },
+        Value::NaiveDateTime(dt) => match type_info {
+            Value::NaiveDate(_) => Ok(Value::NaiveDate(dt.date())),
+            Value::DateTime(_) => Ok(Value::DateTime(as_utc(dt))),
+            _ => Err(UNSUPPORTED),
+        },
+        Value::DateTime(dt) => match type_info {
+            Value::NaiveDate(_) => Ok(Value::NaiveDate(dt.naive_local().date())),
+            Value::NaiveDateTime(_) => Ok(Value::NaiveDateTime(dt.naive_local())),
+            _ => Err(UNSUPPORTED),
+        },
+        _ => Err(UNSUPPORTED),
+    }
+}
+
+fn is_integral_type(type_info: &Value) -> bool {
+    matches!(
+        type_info,
+        Value::Int8(_)
+            | Value::Int16(_)
+            | Value::Int32(_)
+            | Value::Int64(_)
+            | Value::Int128(_)
+            | Value::UInt8(_)
+            | Value::UInt16(_)
+            | Value::UInt32(_)
+            | Value::UInt64(_)
+            | Value::UInt128(_)
+    )
+}
+
+fn integral_to(i: Integral, type_info: &Value) -> std::result::Result<Value, &'static str> {
+    Ok(match type_info {
+        Value::Int8(_) => Value::Int8(i.narrow()?),
+        Value::Int16(_) => Value::Int16(i.narrow()?),
+        Value::Int32(_) => Value::Int32(i.narrow()?),
+        Value::Int64(_) => Value::Int64(i.narrow()?),
+        Value::Int128(_) => Value::Int128(i.narrow()?),
+        Value::UInt8(_) => Value::UInt8(i.narrow()?),
+        Value::UInt16(_) => Value::UInt16(i.narrow()?),
+        Value::UInt32(_) => Value::UInt32(i.narrow()?),
+        Value::UInt64(_) => Value::UInt64(i.narrow()?),
+        Value::UInt128(_) => Value::UInt128(i.narrow()?),
+        Value::Float32(_) => Value::Float32(i.to_f64() as f32),
+        Value::Float64(_) => Value::Float64(i.to_f64()),
+        Value::Decimal(_) => Value::Decimal(i.repr().parse().map_err(|_| OUT_OF_RANGE)?),
+        Value::Bool(_) => match i.narrow::<u8>() {
+            Ok(0) => Value::Bool(false),
+            Ok(1) => Value::Bool(true),
+            _ => return Err(OUT_OF_RANGE),
+        },
+        _ => return Err(UNSUPPORTED),
+    })
+}
+
+/// `repr` is the shortest string representation of the original float, which is used for the
+/// conversion into `Decimal` (so that e.g. `0.1f32` becomes `0.1` and not `0.100000001490116`).
+fn float_to(f: f64, repr: String, type_info: &Value) -> std::result::Result<Value, &'static str> {
+    match type_info {
+        _ if !f.is_finite() && !matches!(type_info, Value::Float32(_) | Value::Float64(_)) => {
+            Err(NOT_FINITE)
+        }
+        Value::Float32(_) if f.is_finite() && f.abs() > f64::from(f32::MAX) => Err(OUT_OF_RANGE),
+        Value::Float32(_) => Ok(Value::Float32(f as f32)),
+        Value::Float64(_) => Ok(Value::Float64(f)),
+        Value::Decimal(_) => repr.parse().map(Value::Decimal).map_err(|_| OUT_OF_RANGE),
+        _ if is_integral_type(type_info) => {
+            if f.fract() != 0.0 {
+                Err(FRACTIONAL)
+            } else if f < 0.0 && f >= i128::MIN as f64 {
+                integral_to(Integral::Signed(f as i128), type_info)
+            } else if f >= 0.0 && f < u128::MAX as f64 {
+                integral_to(Integral::Unsigned(f as u128), type_info)
+            } else {
+                Err(OUT_OF_RANGE)
+            }
+        }
+        _ => Err(UNSUPPORTED),
+    }
+}
+
+fn midnight(d: &NaiveDate) -> std::result::Result<NaiveDateTime, &'static str> {
+    d.and_hms_opt(0, 0, 0).ok_or(OUT_OF_RANGE)
+}
+
+fn as_utc(dt: &NaiveDateTime) -> DateTime<FixedOffset> {
+    FixedOffset::east_opt(0)
+        .expect("0 is a valid offset")
+        .from_utc_datetime(dt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_same_variant_and_to_string() {
+        assert_eq!(
+            Some(Value::Int32(42)),
+            convert(&Value::Int32(42), &Value::int32_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::from("42.5".to_string())),
+            convert(&Value::Float64(42.5), &Value::string_default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_convert_string_and_char() {
+        assert_eq!(
+            Some(Value::UInt8(7)),
+            convert(&Value::from("7".to_string()), &Value::uint8_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::Int8(7)),
+            convert(&Value::Char('7'), &Value::int8_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::Char('7')),
+            convert(&Value::UInt64(7), &Value::char_default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_convert_integers() {
+        assert_eq!(
+            Some(Value::Int64(-3)),
+            convert(&Value::Int8(-3), &Value::int64_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::UInt8(255)),
+            convert(&Value::Int128(255), &Value::uint8_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::Bool(true)),
+            convert(&Value::UInt16(1), &Value::bool_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::Int32(1)),
+            convert(&Value::Bool(true), &Value::int32_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::Float32(-3.0)),
+            convert(&Value::Int16(-3), &Value::float32_default()).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Conversion(ConversionError { msg: \"cannot convert Int32 into UInt8: value out of range\""
+    )]
+    fn test_convert_integer_err_overflow() {
+        convert(&Value::Int32(256), &Value::uint8_default()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Conversion(ConversionError { msg: \"cannot convert Int8 into UInt32: value out of range\""
+    )]
+    fn test_convert_integer_err_negative_into_unsigned() {
+        convert(&Value::Int8(-1), &Value::uint32_default()).unwrap();
+    }
+
+    #[test]
+    fn test_convert_floats_and_decimal() {
+        assert_eq!(
+            Some(Value::Int64(-12)),
+            convert(&Value::Float64(-12.0), &Value::int64_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::Float64(1.5)),
+            convert(&Value::Float32(1.5), &Value::float64_default()).unwrap()
+        );
+        let dec = convert(&Value::Float32(0.1), &Value::decimal_default())
+            .unwrap()
+            .unwrap();
+        assert_eq!("0.1", value_to_string(&dec));
+        assert_eq!(
+            Some(Value::Float64(0.1)),
+            convert(&dec, &Value::float64_default()).unwrap()
+        );
+        let dec = convert(&Value::Int32(120), &Value::decimal_default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Some(Value::UInt8(120)),
+            convert(&dec, &Value::uint8_default()).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Conversion(ConversionError { msg: \"cannot convert Float64 into Int32: value has a fractional part\""
+    )]
+    fn test_convert_float_err_fractional() {
+        convert(&Value::Float64(1.5), &Value::int32_default()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Conversion(ConversionError { msg: \"cannot convert Float64 into Float32: value out of range\""
+    )]
+    fn test_convert_float_err_out_of_f32_range() {
+        convert(&Value::Float64(1e300), &Value::float32_default()).unwrap();
+    }
+
+    #[test]
+    fn test_convert_date_and_date_time() {
+        let date = NaiveDate::from_ymd_opt(2022, 3, 4).unwrap();
+        let date_time = date.and_hms_opt(13, 14, 15).unwrap();
+        assert_eq!(
+            Some(Value::NaiveDateTime(date.and_hms_opt(0, 0, 0).unwrap())),
+            convert(&Value::NaiveDate(date), &Value::naive_date_time_default()).unwrap()
+        );
+        assert_eq!(
+            Some(Value::NaiveDate(date)),
+            convert(
+                &Value::NaiveDateTime(date_time),
+                &Value::naive_date_default()
+            )
+            .unwrap()
+        );
+        let with_offset = DateTime::parse_from_rfc3339("2022-03-04T13:14:15+02:00").unwrap();
+        assert_eq!(
+            Some(Value::NaiveDateTime(date_time)),
+            convert(
+                &Value::DateTime(with_offset),
+                &Value::naive_date_time_default()
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Some(Value::from("2022-03-04T13:14:15+00:00".to_string())),
+            convert(
+                &convert(
+                    &Value::NaiveDateTime(date_time),
+                    &Value::date_time_default()
+                )
+                .unwrap()
+                .unwrap(),
+                &Value::string_default()
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Conversion(ConversionError { msg: \"cannot convert NaiveDate into Int32: unsupported conversion\""
+    )]
+    fn test_convert_err_unsupported() {
+        convert(
+            &Value::NaiveDate(NaiveDate::from_ymd_opt(2022, 3, 4).unwrap()),
+            &Value::int32_default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_convert_with_number_locale_and_affixes() {
+        let opts = ConversionOptions {
+            number_locale: NumberLocale {
+                decimal_sep: ',',
+                thousands_sep: Some('.'),
+            },
+            strip_affixes: vec![String::from("€"), String::from("EUR")],
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(Value::Float64(-1234567.89)),
+            convert_with(
+                &Value::from("€ -1.234.567,89".to_string()),
+                &Value::float64_default(),
+                &opts
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Some(Value::UInt32(1000)),
+            convert_with(
+                &Value::from("1.000 EUR".to_string()),
+                &Value::uint32_default(),
+                &opts
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_convert_with_date_formats_and_fallback() {
+        let opts = ConversionOptions {
+            date_formats: vec![String::from("%d.%m.%Y"), String::from("%d/%m/%Y")],
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2022, 3, 4).unwrap();
+        for s in ["04.03.2022", "04/03/2022", "2022-03-04"] {
+            assert_eq!(
+                Some(Value::NaiveDate(date)),
+                convert_with(
+                    &Value::from(s.to_string()),
+                    &Value::naive_date_default(),
+                    &opts
+                )
+                .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Conversion(ConversionError { msg: \"cannot parse '2022.03.04' into NaiveDate: matches neither the custom nor the default format\""
+    )]
+    fn test_convert_with_date_formats_err_no_match() {
+        let opts = ConversionOptions {
+            date_formats: vec![String::from("%d.%m.%Y")],
+            ..Default::default()
+        };
+        convert_with(
+            &Value::from("2022.03.04".to_string()),
+            &Value::naive_date_default(),
+            &opts,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_convert_with_truthy_falsy() {
+        let opts = ConversionOptions {
+            truthy: vec![String::from("J"), String::from("ja")],
+            falsy: vec![String::from("N"), String::from("nein")],
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(Value::Bool(true)),
+            convert_with(
+                &Value::from("JA".to_string()),
+                &Value::bool_default(),
+                &opts
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Some(Value::Bool(false)),
+            convert_with(&Value::from("n".to_string()), &Value::bool_default(), &opts).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_target_conversions_for_idx() {
+        let opts = ConversionOptions {
+            truthy: vec![String::from("J")],
+            ..Default::default()
+        };
+        let conversions = TargetConversions(vec![(3, opts.clone())]);
+        assert_eq!(&opts, conversions.for_idx(3));
+        assert_eq!(&ConversionOptions::default(), conversions.for_idx(0));
+    }
+
+    #[test]
+    fn test_check_convertible() {
+        check_convertible(&Value::string_default(), &Value::naive_date_default()).unwrap();
+        check_convertible(&Value::int32_default(), &Value::float64_default()).unwrap();
+        check_convertible(&Value::naive_date_default(), &Value::date_time_default()).unwrap();
+        assert!(matches!(
+            check_convertible(&Value::bool_default(), &Value::naive_date_default()),
+            Err(VenumTdsTransRichError::Conversion(_))
+        ));
+    }
+}
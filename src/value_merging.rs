@@ -0,0 +1,339 @@
+use serde::Deserialize;
+use venum::venum::Value;
+
+use crate::{
+    errors::{MergeError, Result, VenumTdsTransRichError},
+    traits::value::{Merge, MergeN},
+    value_conversion::value_to_string,
+};
+
+/// How a `None` source value is treated when merging.
+/// If *all* sources are `None` (and `None` is not forbidden), the merge result is `None` as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeNone {
+    /// Leave the value out, i.e. it contributes neither a token nor a separator.
+    #[default]
+    Skip,
+    /// Treat the value as an empty string.
+    AsEmpty,
+    /// Don't merge, return an error instead.
+    Fail,
+}
+
+/// Stringifies all sources according to `merge_none`. Skipped values are returned as `None`.
+/// Returns `Ok(None)` if there is nothing to merge at all, i.e. all sources are `None`.
+fn stringify_sources(
+    src: &[&Option<Value>],
+    merge_none: MergeNone,
+) -> Result<Option<Vec<Option<String>>>> {
+    if src.iter().all(|v| v.is_none()) && merge_none != MergeNone::Fail {
+        return Ok(None);
+    }
+    src.iter()
+        .map(|v| match (v, merge_none) {
+            (Some(val), _) => Ok(Some(value_to_string(val))),
+            (None, MergeNone::Skip) => Ok(None),
+            (None, MergeNone::AsEmpty) => Ok(Some(String::new())),
+            (None, MergeNone::Fail) => Err(VenumTdsTransRichError::Merge(MergeError::from(
+                String::from("Value is None, but merge_none is Fail"),
+                src.iter().map(|v| (*v).clone()).collect(),
+                None,
+            ))),
+        })
+        .collect::<Result<Vec<Option<String>>>>()
+        .map(Some)
+}
+
+#[derive(Debug)]
+pub struct ValueStringConcatMerge {
+    pub merge_none: MergeNone,
+}
+
+impl ValueStringConcatMerge {
+    fn concat(&self, src: &[&Option<Value>]) -> Result<Option<Value>> {
+        Ok(stringify_sources(src, self.merge_none)?
+            .map(|tokens| Value::String(tokens.into_iter().flatten().collect())))
+    }
+}
+
+impl Merge for ValueStringConcatMerge {
+    fn merge(&self, src_a: &Option<Value>, src_b: &Option<Value>) -> Result<Option<Value>> {
+        self.concat(&[src_a, src_b])
+    }
+}
+
+impl MergeN for ValueStringConcatMerge {
+    fn merge_n(&self, src: Vec<&Option<Value>>) -> Result<Option<Value>> {
+        self.concat(&src)
+    }
+}
+
+#[derive(Debug)]
+pub struct ValueStringJoinMerge {
+    pub separator: String,
+    pub merge_none: MergeNone,
+}
+
+impl ValueStringJoinMerge {
+    fn join(&self, src: &[&Option<Value>]) -> Result<Option<Value>> {
+        Ok(stringify_sources(src, self.merge_none)?.map(|tokens| {
+            Value::String(
+                tokens
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<String>>()
+                    .join(&self.separator),
+            )
+        }))
+    }
+}
+
+impl Merge for ValueStringJoinMerge {
+    fn merge(&self, src_a: &Option<Value>, src_b: &Option<Value>) -> Result<Option<Value>> {
+        self.join(&[src_a, src_b])
+    }
+}
+
+impl MergeN for ValueStringJoinMerge {
+    fn merge_n(&self, src: Vec<&Option<Value>>) -> Result<Option<Value>> {
+        self.join(&src)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TemplateToken {
+    Literal(String),
+    Placeholder(usize),
+}
+
+/// Merges values into a template like `"{0} {1}"`, where `{n}` refers to the n-th source value.
+/// Literal braces are written as `{{` and `}}`. Skipped `None` values are rendered as empty string.
+#[derive(Debug)]
+pub struct ValueTemplateMerge {
+    template: String,
+    pub merge_none: MergeNone,
+    tokens: Vec<TemplateToken>,
+}
+
+impl ValueTemplateMerge {
+    pub fn from(template: String, merge_none: MergeNone) -> Result<Self> {
+        let tokens = Self::parse(&template).map_err(|e| {
+            VenumTdsTransRichError::Merge(MergeError::from(
+                e,
+                Vec::new(),
+                Some(format!(
+                    "template: {} (TemplateMerge, ERROR_ON_TEMPLATE_PARSE)",
+                    template
+                )),
+            ))
+        })?;
+        Ok(ValueTemplateMerge {
+            template,
+            merge_none,
+            tokens,
+        })
+    }
+
+    /// The template, as given to `from`. To merge with a different template, create a new merger.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    fn parse(template: &str) -> std::result::Result<Vec<TemplateToken>, String> {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut num = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(d) if d.is_ascii_digit() => num.push(d),
+                            Some(d) => {
+                                return Err(format!("illegal character '{d}' in placeholder"))
+                            }
+                            None => return Err(String::from("unterminated placeholder")),
+                        }
+                    }
+                    let pos = num
+                        .parse::<usize>()
+                        .map_err(|_| String::from("empty placeholder"))?;
+                    if !literal.is_empty() {
+                        tokens.push(TemplateToken::Literal(std::mem::take(&mut literal)));
+                    }
+                    tokens.push(TemplateToken::Placeholder(pos));
+                }
+                '}' => return Err(String::from("unmatched '}'")),
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            tokens.push(TemplateToken::Literal(literal));
+        }
+        Ok(tokens)
+    }
+
+    fn render(&self, src: &[&Option<Value>]) -> Result<Option<Value>> {
+        let out_of_bounds = self.tokens.iter().find_map(|t| match t {
+            TemplateToken::Placeholder(pos) if *pos >= src.len() => Some(*pos),
+            _ => None,
+        });
+        if let Some(pos) = out_of_bounds {
+            return Err(VenumTdsTransRichError::Merge(MergeError::from(
+                format!(
+                    "template references placeholder {{{pos}}}, but got only {} value(s) to merge",
+                    src.len()
+                ),
+                src.iter().map(|v| (*v).clone()).collect(),
+                Some(format!("template: {}", self.template)),
+            )));
+        }
+
+        Ok(stringify_sources(src, self.merge_none)?.map(|values| {
+            let mut merged = String::new();
+            for t in &self.tokens {
+                match t {
+                    TemplateToken::Literal(l) => merged.push_str(l),
+                    TemplateToken::Placeholder(pos) => {
+                        if let Some(v) = &values[*pos] {
+                            merged.push_str(v)
+                        }
+                    }
+                }
+            }
+            Value::String(merged)
+        }))
+    }
+}
+
+impl Merge for ValueTemplateMerge {
+    fn merge(&self, src_a: &Option<Value>, src_b: &Option<Value>) -> Result<Option<Value>> {
+        self.render(&[src_a, src_b])
+    }
+}
+
+impl MergeN for ValueTemplateMerge {
+    fn merge_n(&self, src: Vec<&Option<Value>>) -> Result<Option<Value>> {
+        self.render(&src)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_concat() {
+        let m = ValueStringConcatMerge {
+            merge_none: MergeNone::Skip,
+        };
+        let res = m
+            .merge(
+                &Some(Value::from("foo".to_string())),
+                &Some(Value::Int32(42)),
+            )
+            .unwrap();
+        assert_eq!(Some(Value::from("foo42".to_string())), res);
+    }
+
+    #[test]
+    fn test_merge_join_skip_none() {
+        let m = ValueStringJoinMerge {
+            separator: String::from(" "),
+            merge_none: MergeNone::Skip,
+        };
+        let a = Some(Value::from("John".to_string()));
+        let b = None;
+        let c = Some(Value::from("Smith".to_string()));
+        let res = m.merge_n(vec![&a, &b, &c]).unwrap();
+        assert_eq!(Some(Value::from("John Smith".to_string())), res);
+    }
+
+    #[test]
+    fn test_merge_join_none_as_empty() {
+        let m = ValueStringJoinMerge {
+            separator: String::from(";"),
+            merge_none: MergeNone::AsEmpty,
+        };
+        let a = Some(Value::from("a".to_string()));
+        let b = None;
+        let c = Some(Value::Bool(true));
+        let res = m.merge_n(vec![&a, &b, &c]).unwrap();
+        assert_eq!(Some(Value::from("a;;true".to_string())), res);
+    }
+
+    #[test]
+    fn test_merge_join_all_none() {
+        let m = ValueStringJoinMerge {
+            separator: String::from(" "),
+            merge_none: MergeNone::AsEmpty,
+        };
+        let res = m.merge(&None, &None).unwrap();
+        assert_eq!(None, res);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Merge(MergeError { msg: \"Value is None, but merge_none is Fail\", src_vals: [Some(String(\"foo\")), None], details: None })"
+    )]
+    fn test_merge_join_none_fail() {
+        let m = ValueStringJoinMerge {
+            separator: String::from(" "),
+            merge_none: MergeNone::Fail,
+        };
+        m.merge(&Some(Value::from("foo".to_string())), &None)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_merge_template() {
+        let m =
+            ValueTemplateMerge::from(String::from("{1}, {0} {{{0}}}"), MergeNone::Skip).unwrap();
+        let res = m
+            .merge(
+                &Some(Value::from("John".to_string())),
+                &Some(Value::from("Smith".to_string())),
+            )
+            .unwrap();
+        assert_eq!(Some(Value::from("Smith, John {John}".to_string())), res);
+        assert_eq!("{1}, {0} {{{0}}}", m.template());
+    }
+
+    #[test]
+    fn test_merge_n_template_skip_none() {
+        let m = ValueTemplateMerge::from(String::from("{0}-{1}-{2}"), MergeNone::Skip).unwrap();
+        let a = Some(Value::UInt16(2022));
+        let b = None;
+        let c = Some(Value::Char('x'));
+        let res = m.merge_n(vec![&a, &b, &c]).unwrap();
+        assert_eq!(Some(Value::from("2022--x".to_string())), res);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Merge(MergeError { msg: \"template references placeholder {2}, but got only 2 value(s) to merge\""
+    )]
+    fn test_merge_template_err_placeholder_out_of_bounds() {
+        let m = ValueTemplateMerge::from(String::from("{0}{2}"), MergeNone::Skip).unwrap();
+        m.merge(&None, &None).unwrap();
+    }
+
+    #[test]
+    fn test_merge_template_illegal_template() {
+        assert!(ValueTemplateMerge::from(String::from("{0"), MergeNone::Skip).is_err());
+        assert!(ValueTemplateMerge::from(String::from("{a}"), MergeNone::Skip).is_err());
+        assert!(ValueTemplateMerge::from(String::from("{}"), MergeNone::Skip).is_err());
+        assert!(ValueTemplateMerge::from(String::from("0}"), MergeNone::Skip).is_err());
+    }
+}
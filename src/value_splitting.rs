@@ -1,9 +1,14 @@
+use std::{borrow::Cow, ops::Range};
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use regex::Regex;
+use serde::Deserialize;
 use venum::venum::Value;
 
 use crate::{
     errors::{Result, VenumTdsTransRichError, SplitError},
-    traits::value::{SplitN, Split},
+    traits::value::{Split, SplitKeyed, SplitN, SplitNNamed},
+    value_conversion::value_to_string,
 };
 
 #[derive(Debug)]
@@ -77,7 +82,7 @@ impl SplitN for ValueStringSeparatorCharSplitN {
                     Value::String(s) => {
                         match s.is_empty() {
                             true => Err(VenumTdsTransRichError::Split(SplitError::minim(String::from(
-                                "Source Value is empty string. Can't split.", // TODO: None!?
+                                "Source Value is empty string. Can't split.", // see `WithTokenPolicy` for alternatives
                             )))),
                             false => {
                                 let splitted: Vec<&str> = s.split(self.sep_char).collect(); // this will never return a length of 0, as it's implemented by rust!
@@ -161,156 +166,2143 @@ impl Split for ValueStringRegexPairSplit {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Splits a `Value::String` into one token per capture group of the regex.
+/// Optional groups, that don't participate in the match, result in `None`.
+#[derive(Debug)]
+pub struct ValueStringRegexSplitN {
+    pub re: Regex,
+    pub split_none: bool,
+}
 
-    #[test]
-    fn test_split_seperator_char() {
-        let sep = ValueStringSeparatorCharSplit {
-            sep_char: ' ',
-            split_none: true,
-        };
-        let data = Some(Value::from("foo bar".to_string()));
-        let split_res = sep.split(&data);
-        assert!(split_res.is_ok());
-        let split_vals = split_res.unwrap();
-        assert_eq!(Some(Value::from("foo".to_string())), split_vals.0);
-        assert_eq!(Some(Value::from("bar".to_string())), split_vals.1);
+impl ValueStringRegexSplitN {
+    pub fn from(regex_pattern: String, split_none: bool) -> Result<Self> {
+        let re = Regex::new(regex_pattern.as_str()).map_err(|e| {
+            let mut err_msg = format!("{}", e);
+            err_msg.push_str(" (RegexSplitterN, ERROR_ON_REGEX_COMPILE)");
+            VenumTdsTransRichError::Split(SplitError::minim(err_msg))
+        })?;
+        if re.captures_len() < 2 {
+            return Err(VenumTdsTransRichError::Split(SplitError::from(
+                String::from("regex has no capture groups. Can't split."),
+                None,
+                Some(format!("regex: {}", re.as_str())),
+            )));
+        }
+        Ok(ValueStringRegexSplitN { re, split_none })
     }
+}
 
-    #[test]
-    fn test_split_seperator_char_none() {
-        let sep = ValueStringSeparatorCharSplit {
-            sep_char: ' ',
-            split_none: true,
-        };
-        let data = None;
-        let split_res = sep.split(&data);
-        assert!(split_res.is_ok());
-        let split_vals = split_res.unwrap();
-        assert_eq!(None, split_vals.0);
-        assert_eq!(None, split_vals.1);
+impl SplitN for ValueStringRegexSplitN {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        match src {
+            Some(Value::String(s)) => {
+                let caps = self.re.captures(s).ok_or_else(|| {
+                    VenumTdsTransRichError::Split(SplitError::from(
+                        String::from("No captures."),
+                        src.clone(),
+                        Some(format!("regex: {}", self.re.as_str())),
+                    ))
+                })?;
+                Ok((1..caps.len())
+                    .map(|i| caps.get(i).map(|m| Value::String(String::from(m.as_str()))))
+                    .collect())
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+            None if self.split_none => Ok(vec![None; self.re.captures_len() - 1]),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
     }
+}
 
-    #[test]
-    #[should_panic(
-        expected = "Split(SplitError { msg: \"expected 2 tokens as result of split, but got:"
-    )]
-    fn test_split_seperator_char_err() {
-        let sep = ValueStringSeparatorCharSplit {
-            sep_char: ' ',
-            split_none: true,
-        };
-        let data = Some(Value::from("foo bar baz".to_string()));
-        sep.split(&data).unwrap();
+impl SplitNNamed for ValueStringRegexSplitN {
+    fn token_names(&self) -> Vec<Option<String>> {
+        self.re
+            .capture_names()
+            .skip(1)
+            .map(|n| n.map(String::from))
+            .collect()
     }
+}
 
-    #[test]
-    #[should_panic(
-        expected = "Split(SplitError { msg: \"expected 2 tokens as result of split, but got: 1\", src_val: Some(String(\"foo\")), details: None })"
-    )]
-    fn test_split_seperator_char_err2() {
-        let sep = ValueStringSeparatorCharSplit {
-            sep_char: ' ',
-            split_none: true,
-        };
-        let data = Some(Value::from("foo".to_string()));
-        sep.split(&data).unwrap();
+/// What to split at. Empty matches of a regex separator are ignored.
+#[derive(Debug, Clone)]
+pub enum Separator {
+    Char(char),
+    Str(String),
+    Regex(Regex),
+}
+
+impl Separator {
+    pub fn regex(regex_pattern: String) -> Result<Self> {
+        let re = Regex::new(regex_pattern.as_str()).map_err(|e| {
+            let mut err_msg = format!("{}", e);
+            err_msg.push_str(" (SeparatorSplitter, ERROR_ON_REGEX_COMPILE)");
+            VenumTdsTransRichError::Split(SplitError::minim(err_msg))
+        })?;
+        Ok(Separator::Regex(re))
     }
 
-    #[test]
-    fn test_split_n_seperator_char() {
-        let sep = ValueStringSeparatorCharSplitN {
-            sep_char: ' ',
-            split_none: false,
-            split_none_into_num_clones: None
-        };
-        let data = Some(Value::from("foo bar baz".to_string()));
-        let split_res = sep.split_n(&data);
-        assert!(split_res.is_ok());
-        let split_vals = split_res.unwrap();
-        assert_eq!(&Some(Value::from("foo".to_string())), split_vals.get(0).unwrap());
-        assert_eq!(&Some(Value::from("bar".to_string())), split_vals.get(1).unwrap());
-        assert_eq!(&Some(Value::from("baz".to_string())), split_vals.get(2).unwrap());
+    /// Byte ranges of all (non-overlapping) occurrences of the separator in `s`, left to right.
+    fn occurrences(&self, s: &str) -> Result<Vec<Range<usize>>> {
+        match self {
+            Separator::Char(c) => Ok(s.match_indices(*c).map(|(i, m)| i..i + m.len()).collect()),
+            Separator::Str(sep) if sep.is_empty() => Err(VenumTdsTransRichError::Split(
+                SplitError::minim(String::from("Separator is empty string. Can't split.")),
+            )),
+            Separator::Str(sep) => Ok(s
+                .match_indices(sep.as_str())
+                .map(|(i, m)| i..i + m.len())
+                .collect()),
+            Separator::Regex(re) => Ok(re
+                .find_iter(s)
+                .filter(|m| !m.range().is_empty())
+                .map(|m| m.range())
+                .collect()),
+        }
     }
+}
 
-    #[test]
-    fn test_split_n_seperator_char_none() {
-        let sep = ValueStringSeparatorCharSplitN {
-            sep_char: ' ',
-            split_none: true,
-            split_none_into_num_clones: Some(3),
-        };
-        let data = None;
-        let split_res = sep.split_n(&data);
-        assert!(split_res.is_ok());
-        let split_vals = split_res.unwrap();
-        assert_eq!(&None, split_vals.get(0).unwrap());
-        assert_eq!(&None, split_vals.get(1).unwrap());
-        assert_eq!(&None, split_vals.get(2).unwrap());
+/// Which occurrence of the separator a pair split divides at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitPosition {
+    #[default]
+    First,
+    Last,
+    /// The n-th occurrence, counted from 1 (`Nth(1)` is the same as `First`).
+    Nth(usize),
+}
+
+/// Divides a `Value::String` into two tokens at the configured occurrence of the separator,
+/// e.g. `Last` with `Separator::Char('.')` splits `"file.tar.gz"` into `"file.tar"` and `"gz"`.
+/// Other occurrences of the separator are kept as they are.
+#[derive(Debug)]
+pub struct ValueStringSeparatorPairSplit {
+    pub separator: Separator,
+    pub position: SplitPosition,
+    pub split_none: bool,
+}
+
+impl Split for ValueStringSeparatorPairSplit {
+    fn split(&self, src: &Option<Value>) -> Result<(Option<Value>, Option<Value>)> {
+        match src {
+            Some(Value::String(s)) => {
+                let occurrences = self.separator.occurrences(s)?;
+                let at = match self.position {
+                    SplitPosition::First => occurrences.first(),
+                    SplitPosition::Last => occurrences.last(),
+                    SplitPosition::Nth(n) => n.checked_sub(1).and_then(|i| occurrences.get(i)),
+                };
+                let at = at.ok_or_else(|| {
+                    VenumTdsTransRichError::Split(SplitError::from(
+                        format!(
+                            "separator occurrence {:?} not found, got {} occurrence(s)",
+                            self.position,
+                            occurrences.len()
+                        ),
+                        src.clone(),
+                        Some(format!("separator: {:?}", self.separator)),
+                    ))
+                })?;
+                Ok((
+                    Some(Value::String(String::from(&s[..at.start]))),
+                    Some(Value::String(String::from(&s[at.end..]))),
+                ))
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+            None if self.split_none => Ok((None, None)),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
     }
+}
 
-    #[test]
-    #[should_panic(
-        expected = "Split(SplitError { msg: \"Value is None, split_none is true, but split_none_into_num_clones is not set. Can't split into undefined number of targets!\", src_val: None, details: None })"
-    )]
-    fn test_split_n_seperator_char_none_err_config() {
-        let sep = ValueStringSeparatorCharSplitN {
-            sep_char: ' ',
-            split_none: true,
-            split_none_into_num_clones: None,
+/// Limits the number of splits, like `str::splitn` (`FromLeft`) and `str::rsplitn` (`FromRight`).
+/// The number is the maximum number of *splits*, so at most n + 1 tokens are produced.
+/// Unlike `rsplitn`, tokens are always returned in their original order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaxSplits {
+    #[default]
+    Unlimited,
+    FromLeft(usize),
+    FromRight(usize),
+}
+
+#[derive(Debug)]
+pub struct ValueStringSeparatorSplitN {
+    pub separator: Separator,
+    pub max_splits: MaxSplits,
+    pub split_none: bool,
+    pub split_none_into_num_clones: Option<usize>,
+}
+
+impl SplitN for ValueStringSeparatorSplitN {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        match src {
+            Some(Value::String(s)) if s.is_empty() => {
+                Err(VenumTdsTransRichError::Split(SplitError::minim(String::from(
+                    "Source Value is empty string. Can't split.", // see `WithTokenPolicy` for alternatives
+                ))))
+            }
+            Some(Value::String(s)) => {
+                let mut occurrences = self.separator.occurrences(s)?;
+                match self.max_splits {
+                    MaxSplits::Unlimited => {}
+                    MaxSplits::FromLeft(n) => occurrences.truncate(n),
+                    MaxSplits::FromRight(n) => {
+                        occurrences.drain(..occurrences.len().saturating_sub(n));
+                    }
+                }
+                if occurrences.is_empty() {
+                    return Err(VenumTdsTransRichError::Split(SplitError::from(
+                        String::from("expected 2 (or more) tokens as result of split, but got: 1"),
+                        src.clone(),
+                        Some(format!(
+                            "separator: {:?}, max_splits: {:?}",
+                            self.separator, self.max_splits
+                        )),
+                    )));
+                }
+                let mut tokens = Vec::with_capacity(occurrences.len() + 1);
+                let mut start = 0;
+                for r in occurrences {
+                    tokens.push(Some(Value::String(String::from(&s[start..r.start]))));
+                    start = r.end;
+                }
+                tokens.push(Some(Value::String(String::from(&s[start..]))));
+                Ok(tokens)
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+            None => match (self.split_none, self.split_none_into_num_clones) {
+                (true, Some(num_targets)) => Ok(vec![None; num_targets]),
+                (true, None) => Err(VenumTdsTransRichError::Split(SplitError::minim(String::from(
+                    "Value is None, split_none is true, but split_none_into_num_clones is not set. Can't split into undefined number of targets!",
+                )))),
+                (false, _) => Err(VenumTdsTransRichError::Split(SplitError::minim(String::from(
+                    "Value is None but split_none is false. Not allowed to split!",
+                )))),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixedWidthUnit {
+    #[default]
+    Chars,
+    Bytes,
+}
+
+/// What to do with records, that are shorter than the end of the last field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixedWidthShortRecord {
+    #[default]
+    Fail,
+    /// Truncated fields get what's there, fields that are missing entirely become `None`.
+    Pad,
+}
+
+/// Splits a fixed-width (positional) record into one token per `[start, end)` range.
+#[derive(Debug)]
+pub struct ValueStringFixedWidthSplitN {
+    pub ranges: Vec<Range<usize>>,
+    pub unit: FixedWidthUnit,
+    /// Trim (whitespace) padding off the tokens.
+    pub trim: bool,
+    /// Tokens that are empty or contain only whitespace become `None`.
+    pub blank_as_none: bool,
+    pub short_record: FixedWidthShortRecord,
+    pub split_none: bool,
+}
+
+impl ValueStringFixedWidthSplitN {
+    /// Turns consecutive column widths into `[start, end)` ranges, i.e. `[2, 3]` into `[0..2, 2..5]`.
+    pub fn ranges_from_widths(widths: &[usize]) -> Vec<Range<usize>> {
+        let mut start = 0;
+        widths
+            .iter()
+            .map(|w| {
+                let r = start..start + w;
+                start += w;
+                r
+            })
+            .collect()
+    }
+
+    fn field(&self, s: &str, chars: &[char], range: &Range<usize>) -> Result<Option<String>> {
+        let len = match self.unit {
+            FixedWidthUnit::Chars => chars.len(),
+            FixedWidthUnit::Bytes => s.len(),
         };
-        let data = None;
-        sep.split_n(&data).unwrap();
+        if range.start > range.end {
+            return Err(VenumTdsTransRichError::Split(SplitError::from(
+                format!("illegal field range: {:?}", range),
+                Some(Value::String(String::from(s))),
+                None,
+            )));
+        }
+        if range.end > len && self.short_record == FixedWidthShortRecord::Fail {
+            return Err(VenumTdsTransRichError::Split(SplitError::from(
+                format!(
+                    "record too short. Field {:?} exceeds record length: {}",
+                    range, len
+                ),
+                Some(Value::String(String::from(s))),
+                None,
+            )));
+        }
+        // only a zero-width field at the very end of the record is still part of it
+        if range.start > len || (range.start == len && range.start != range.end) {
+            return Ok(None);
+        }
+
+        let end = range.end.min(len);
+        let token = match self.unit {
+            FixedWidthUnit::Chars => chars[range.start..end].iter().collect::<String>(),
+            FixedWidthUnit::Bytes => String::from(s.get(range.start..end).ok_or_else(|| {
+                VenumTdsTransRichError::Split(SplitError::from(
+                    format!("field {:?} is not on a char boundary", range),
+                    Some(Value::String(String::from(s))),
+                    None,
+                ))
+            })?),
+        };
+
+        match (self.trim, self.blank_as_none) {
+            (_, true) if token.trim().is_empty() => Ok(None),
+            (true, _) => Ok(Some(String::from(token.trim()))),
+            (false, _) => Ok(Some(token)),
+        }
     }
+}
 
-    #[test]
-    fn test_split_n_regex_pair() {
-        let sep_res =
-            ValueStringRegexPairSplit::from("(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(), true);
-        assert!(sep_res.is_ok());
-        let sep = sep_res.unwrap();
+impl SplitN for ValueStringFixedWidthSplitN {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        match src {
+            Some(Value::String(s)) => {
+                let chars: Vec<char> = match self.unit {
+                    FixedWidthUnit::Chars => s.chars().collect(),
+                    FixedWidthUnit::Bytes => Vec::new(),
+                };
+                self.ranges
+                    .iter()
+                    .map(|r| Ok(self.field(s, &chars, r)?.map(Value::String)))
+                    .collect()
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+            None if self.split_none => Ok(vec![None; self.ranges.len()]),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
+    }
+}
 
-        let data = Some(Value::from("1.12 2.23".to_string()));
-        let split_res = sep.split(&data);
-        assert!(split_res.is_ok());
-        let split_vals = split_res.unwrap();
-        assert_eq!(Some(Value::from("1.12".to_string())), split_vals.0);
-        assert_eq!(Some(Value::from("2.23".to_string())), split_vals.1);
+/// CSV-like quoting/escaping rules, used by the quote-aware separator splitters.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeparatorQuoting {
+    pub quote_char: char,
+    pub escape_char: Option<char>,
+    /// Two consecutive quote chars inside a quoted token are a literal quote char, i.e. `"a ""b"""`
+    pub double_quote_escape: bool,
+    /// Remove the quotes and resolve escapes in the resulting tokens. Otherwise tokens are returned as is.
+    pub unquote: bool,
+}
+
+impl Default for SeparatorQuoting {
+    fn default() -> Self {
+        Self {
+            quote_char: '"',
+            escape_char: None,
+            double_quote_escape: true,
+            unquote: true,
+        }
     }
+}
 
-    #[test]
-    #[should_panic(
-        expected = "Split(SplitError { msg: \"No captures, but we need exactly two.\""
-    )]
-    fn test_split_n_regex_err_no_captures() {
-        let sep_res =
-            ValueStringRegexPairSplit::from("(\\d+\\.\\d+).*(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(), true);
-        assert!(sep_res.is_ok());
-        let sep = sep_res.unwrap();
+impl SeparatorQuoting {
+    /// Splits `s` at every occurrence of `sep_char` that is neither quoted nor escaped.
+    pub fn tokenize(&self, s: &str, sep_char: char) -> std::result::Result<Vec<String>, String> {
+        self.tokenize_impl(s, sep_char, usize::MAX, self.unquote)
+    }
 
-        let data = Some(Value::from("1.12 2.23".to_string()));
-        sep.split(&data).unwrap();
+    /// Like `tokenize`, but returns at most `max_tokens` tokens. The last token holds the (unsplit) rest.
+    pub fn tokenize_n(
+        &self,
+        s: &str,
+        sep_char: char,
+        max_tokens: usize,
+    ) -> std::result::Result<Vec<String>, String> {
+        self.tokenize_impl(s, sep_char, max_tokens, self.unquote)
     }
 
-    #[test]
-    #[should_panic(
-        expected = "Split(SplitError { msg: \"1 capture group(s), but we need exactly two.\""
-    )]
-    fn test_split_n_regex_err_too_few_capture_groups() {
-        let sep_res =
-            ValueStringRegexPairSplit::from("(\\d+\\.\\d+)".to_string(), true);
-        assert!(sep_res.is_ok());
-        let sep = sep_res.unwrap();
+    fn tokenize_impl(
+        &self,
+        s: &str,
+        sep_char: char,
+        max_tokens: usize,
+        unquote: bool,
+    ) -> std::result::Result<Vec<String>, String> {
+        let mut tokens = Vec::new();
+        let mut token = String::new();
+        let mut in_quotes = false;
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if Some(c) == self.escape_char {
+                let escaped = chars
+                    .next()
+                    .ok_or_else(|| format!("dangling escape char '{c}' at end of value"))?;
+                if !unquote {
+                    token.push(c);
+                }
+                token.push(escaped);
+            } else if c == self.quote_char {
+                if in_quotes && self.double_quote_escape && chars.peek() == Some(&self.quote_char) {
+                    chars.next();
+                    if !unquote {
+                        token.push(c);
+                    }
+                    token.push(c);
+                } else {
+                    in_quotes = !in_quotes;
+                    if !unquote {
+                        token.push(c);
+                    }
+                }
+            } else if c == sep_char && !in_quotes && tokens.len() + 1 < max_tokens {
+                tokens.push(std::mem::take(&mut token));
+            } else {
+                token.push(c);
+            }
+        }
+        if in_quotes {
+            return Err(format!("unterminated quote ({})", self.quote_char));
+        }
+        tokens.push(token);
+        Ok(tokens)
+    }
 
-        let data = Some(Value::from("1.12 2.23".to_string()));
-        sep.split(&data).unwrap();
+    /// Quotes `s`, if it contains any of the `special` chars (or the quote/escape char).
+    pub fn quote(&self, s: &str, special: &[char]) -> String {
+        let needs_quoting = s
+            .chars()
+            .any(|c| c == self.quote_char || Some(c) == self.escape_char || special.contains(&c));
+        if !needs_quoting {
+            return String::from(s);
+        }
+        let mut quoted = String::with_capacity(s.len() + 2);
+        quoted.push(self.quote_char);
+        for c in s.chars() {
+            if c == self.quote_char || Some(c) == self.escape_char {
+                match (self.double_quote_escape, self.escape_char) {
+                    (true, _) if c == self.quote_char => quoted.push(self.quote_char),
+                    (_, Some(e)) => quoted.push(e),
+                    _ => {}
+                }
+            }
+            quoted.push(c);
+        }
+        quoted.push(self.quote_char);
+        quoted
     }
+}
 
-    #[test]
-    fn test_split_n_regex_pair_illegal_regex() {
-        let sep_res = ValueStringRegexPairSplit::from("FWPUJWDJW/)!(!()?))".to_string(), true);
-        assert!(sep_res.is_err());
+#[derive(Debug)]
+pub struct ValueStringSeparatorCharQuotedSplit {
+    pub sep_char: char,
+    pub quoting: SeparatorQuoting,
+    pub split_none: bool,
+}
+
+impl Split for ValueStringSeparatorCharQuotedSplit {
+    fn split(&self, src: &Option<Value>) -> Result<(Option<Value>, Option<Value>)> {
+        match src {
+            Some(Value::String(s)) => {
+                let mut splitted = self.quoting.tokenize(s, self.sep_char).map_err(|e| {
+                    VenumTdsTransRichError::Split(SplitError::from(e, src.clone(), None))
+                })?;
+                if splitted.len() != 2 {
+                    return Err(VenumTdsTransRichError::Split(SplitError::from(
+                        format!(
+                            "expected 2 tokens as result of split, but got: {}",
+                            splitted.len()
+                        ),
+                        src.clone(),
+                        None,
+                    )));
+                }
+                let right = splitted.pop();
+                let left = splitted.pop();
+                Ok((left.map(Value::String), right.map(Value::String)))
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+            None if self.split_none => Ok((None, None)),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ValueStringSeparatorCharQuotedSplitN {
+    pub sep_char: char,
+    pub quoting: SeparatorQuoting,
+    pub split_none: bool,
+    pub split_none_into_num_clones: Option<usize>,
+}
+
+impl SplitN for ValueStringSeparatorCharQuotedSplitN {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        match src {
+            None => match (&self.split_none, &self.split_none_into_num_clones) {
+                (true, None) => Err(VenumTdsTransRichError::Split(SplitError::minim(String::from(
+                    "Value is None, split_none is true, but split_none_into_num_clones is not set. Can't split into undefined number of targets!",
+                )))),
+                (false, _) => Err(VenumTdsTransRichError::Split(SplitError::minim(String::from(
+                    "Value is None but split_none is false. Not allowed to split!",
+                )))),
+                (true, Some(num_targets)) => Ok(vec![None; *num_targets]),
+            },
+            Some(Value::String(s)) if s.is_empty() => Err(VenumTdsTransRichError::Split(
+                SplitError::minim(String::from("Source Value is empty string. Can't split.")),
+            )),
+            Some(Value::String(s)) => {
+                let splitted = self.quoting.tokenize(s, self.sep_char).map_err(|e| {
+                    VenumTdsTransRichError::Split(SplitError::from(e, src.clone(), None))
+                })?;
+                match splitted.len() {
+                    1 => Err(VenumTdsTransRichError::Split(SplitError::from(
+                        String::from("expected 2 (or more) tokens as result of split, but got: 1"),
+                        src.clone(),
+                        None,
+                    ))),
+                    _ => Ok(splitted
+                        .into_iter()
+                        .map(|v| Some(Value::String(v)))
+                        .collect()),
+                }
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+        }
+    }
+}
+
+/// Extracts values from a JSON document (in a `Value::String`), one token per path.
+/// Paths are either JSON pointers (`/items/0/name`) or dotted paths (`items.0.name`).
+/// Numbers and bools are mapped to the matching `Value` variants, nested arrays/objects are returned
+/// as (re-serialized) JSON strings. `null` results in `None`, as do missing paths, if `missing_as_none`.
+#[derive(Debug)]
+pub struct ValueStringJsonSplitN {
+    paths: Vec<String>,
+    pub missing_as_none: bool,
+    pub split_none: bool,
+    pointers: Vec<String>,
+}
+
+impl ValueStringJsonSplitN {
+    pub fn from(paths: Vec<String>, missing_as_none: bool, split_none: bool) -> Result<Self> {
+        let pointers = paths
+            .iter()
+            .map(|p| Self::to_pointer(p))
+            .collect::<Result<Vec<String>>>()?;
+        Ok(ValueStringJsonSplitN {
+            paths,
+            missing_as_none,
+            split_none,
+            pointers,
+        })
+    }
+
+    /// The paths, as given to `from`. To split with different paths, create a new splitter.
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    fn to_pointer(path: &str) -> Result<String> {
+        if path.is_empty() || path.starts_with('/') {
+            return Ok(String::from(path));
+        }
+        path.split('.')
+            .map(|segment| match segment.is_empty() {
+                true => Err(VenumTdsTransRichError::Split(SplitError::from(
+                    String::from("illegal path, empty segment"),
+                    None,
+                    Some(format!("path: {}", path)),
+                ))),
+                false => Ok(format!(
+                    "/{}",
+                    segment.replace('~', "~0").replace('/', "~1")
+                )),
+            })
+            .collect()
+    }
+
+    fn json_to_value(json: &serde_json::Value) -> Option<Value> {
+        match json {
+            serde_json::Value::Null => None,
+            serde_json::Value::Bool(b) => Some(Value::Bool(*b)),
+            serde_json::Value::Number(n) => match (n.as_i64(), n.as_u64(), n.as_f64()) {
+                (Some(i), _, _) => Some(Value::Int64(i)),
+                (None, Some(u), _) => Some(Value::UInt64(u)),
+                (None, None, Some(f)) => Some(Value::Float64(f)),
+                (None, None, None) => Some(Value::String(n.to_string())),
+            },
+            serde_json::Value::String(s) => Some(Value::String(s.clone())),
+            nested => Some(Value::String(nested.to_string())),
+        }
+    }
+}
+
+impl SplitN for ValueStringJsonSplitN {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        match src {
+            Some(Value::String(s)) => {
+                let doc: serde_json::Value = serde_json::from_str(s).map_err(|e| {
+                    VenumTdsTransRichError::Split(SplitError::from(
+                        String::from("Not a valid JSON document. Can't split."),
+                        src.clone(),
+                        Some(e.to_string()),
+                    ))
+                })?;
+                self.pointers
+                    .iter()
+                    .zip(self.paths.iter())
+                    .map(|(pointer, path)| match doc.pointer(pointer) {
+                        Some(json) => Ok(Self::json_to_value(json)),
+                        None if self.missing_as_none => Ok(None),
+                        None => Err(VenumTdsTransRichError::Split(SplitError::from(
+                            String::from("path not found in JSON document"),
+                            src.clone(),
+                            Some(format!("path: {}", path)),
+                        ))),
+                    })
+                    .collect()
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+            None if self.split_none => Ok(vec![None; self.paths.len()]),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
+    }
+}
+
+impl SplitNNamed for ValueStringJsonSplitN {
+    fn token_names(&self) -> Vec<Option<String>> {
+        self.paths.iter().cloned().map(Some).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UrlComponent {
+    Scheme,
+    User,
+    Password,
+    Host,
+    /// The explicit port, or the default port of the scheme (e.g. 443 for https), as `Value::UInt16`.
+    Port,
+    Path,
+    Query,
+    Fragment,
+    /// The (percent-decoded) value of the first query parameter with the given name.
+    QueryParam(String),
+}
+
+impl UrlComponent {
+    pub fn name(&self) -> String {
+        match self {
+            UrlComponent::Scheme => String::from("scheme"),
+            UrlComponent::User => String::from("user"),
+            UrlComponent::Password => String::from("password"),
+            UrlComponent::Host => String::from("host"),
+            UrlComponent::Port => String::from("port"),
+            UrlComponent::Path => String::from("path"),
+            UrlComponent::Query => String::from("query"),
+            UrlComponent::Fragment => String::from("fragment"),
+            UrlComponent::QueryParam(param) => format!("query.{}", param),
+        }
+    }
+}
+
+/// Decomposes an (absolute) URL into the selected components, one token per component.
+/// Components that are not present in the URL result in `None`.
+#[derive(Debug)]
+pub struct ValueStringUrlSplitN {
+    pub components: Vec<UrlComponent>,
+    pub split_none: bool,
+}
+
+impl ValueStringUrlSplitN {
+    fn component(url: &url::Url, component: &UrlComponent) -> Option<Value> {
+        let non_empty = |s: &str| match s.is_empty() {
+            true => None,
+            false => Some(Value::String(String::from(s))),
+        };
+        match component {
+            UrlComponent::Scheme => non_empty(url.scheme()),
+            UrlComponent::User => non_empty(url.username()),
+            UrlComponent::Password => url.password().and_then(non_empty),
+            UrlComponent::Host => url.host_str().and_then(non_empty),
+            UrlComponent::Port => url.port_or_known_default().map(Value::UInt16),
+            UrlComponent::Path => non_empty(url.path()),
+            UrlComponent::Query => url.query().and_then(non_empty),
+            UrlComponent::Fragment => url.fragment().and_then(non_empty),
+            UrlComponent::QueryParam(param) => url
+                .query_pairs()
+                .find(|(name, _)| name == param)
+                .map(|(_, v)| Value::String(v.into_owned())),
+        }
+    }
+}
+
+impl SplitN for ValueStringUrlSplitN {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        match src {
+            Some(Value::String(s)) => {
+                let url = url::Url::parse(s).map_err(|e| {
+                    VenumTdsTransRichError::Split(SplitError::from(
+                        String::from("Not a valid URL. Can't split."),
+                        src.clone(),
+                        Some(e.to_string()),
+                    ))
+                })?;
+                Ok(self
+                    .components
+                    .iter()
+                    .map(|c| Self::component(&url, c))
+                    .collect())
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+            None if self.split_none => Ok(vec![None; self.components.len()]),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
+    }
+}
+
+impl SplitNNamed for ValueStringUrlSplitN {
+    fn token_names(&self) -> Vec<Option<String>> {
+        self.components.iter().map(|c| Some(c.name())).collect()
+    }
+}
+
+/// What to do, if a key occurs more than once in a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKeys {
+    #[default]
+    Fail,
+    /// Keep the value of the first occurrence.
+    First,
+    /// Keep the value of the last occurrence (at the position of the first one).
+    Last,
+}
+
+/// Splits key/value pairs like `color=red;size=XL`, with an optional quoting for keys and values.
+/// Empty pairs are skipped, a pair without `kv_sep` results in the key with a `None` value.
+#[derive(Debug)]
+pub struct ValueStringKeyValueSplit {
+    pub pair_sep: char,
+    pub kv_sep: char,
+    pub quoting: Option<SeparatorQuoting>,
+    pub duplicate_keys: DuplicateKeys,
+    pub split_none: bool,
+}
+
+impl ValueStringKeyValueSplit {
+    fn split_pair(&self, pair: &str) -> std::result::Result<(String, Option<String>), String> {
+        match &self.quoting {
+            Some(q) => {
+                let mut kv = q.tokenize_n(pair, self.kv_sep, 2)?;
+                let v = if kv.len() == 2 { kv.pop() } else { None };
+                Ok((kv.pop().unwrap_or_default(), v))
+            }
+            None => Ok(match pair.split_once(self.kv_sep) {
+                Some((k, v)) => (String::from(k), Some(String::from(v))),
+                None => (String::from(pair), None),
+            }),
+        }
+    }
+
+    fn dedup(
+        &self,
+        pairs: Vec<(String, Option<Value>)>,
+        src: &Option<Value>,
+    ) -> Result<Vec<(String, Option<Value>)>> {
+        let mut unique: Vec<(String, Option<Value>)> = Vec::with_capacity(pairs.len());
+        for (key, val) in pairs {
+            match unique.iter_mut().find(|(k, _)| *k == key) {
+                None => unique.push((key, val)),
+                Some(_) if self.duplicate_keys == DuplicateKeys::First => {}
+                Some(existing) if self.duplicate_keys == DuplicateKeys::Last => existing.1 = val,
+                Some(_) => {
+                    return Err(VenumTdsTransRichError::Split(SplitError::from(
+                        format!("duplicate key: {}", key),
+                        src.clone(),
+                        None,
+                    )))
+                }
+            }
+        }
+        Ok(unique)
+    }
+}
+
+impl SplitKeyed for ValueStringKeyValueSplit {
+    fn split_keyed(&self, src: &Option<Value>) -> Result<Vec<(String, Option<Value>)>> {
+        match src {
+            Some(Value::String(s)) => {
+                let pairs = match &self.quoting {
+                    Some(q) => q.tokenize_impl(s, self.pair_sep, usize::MAX, false),
+                    None => Ok(s.split(self.pair_sep).map(String::from).collect()),
+                }
+                .map_err(|e| {
+                    VenumTdsTransRichError::Split(SplitError::from(e, src.clone(), None))
+                })?;
+                let pairs = pairs
+                    .iter()
+                    .filter(|p| !p.is_empty())
+                    .map(|p| {
+                        self.split_pair(p)
+                            .map(|(k, v)| (k, v.map(Value::String)))
+                            .map_err(|e| {
+                                VenumTdsTransRichError::Split(SplitError::from(
+                                    e,
+                                    src.clone(),
+                                    Some(format!("pair: {}", p)),
+                                ))
+                            })
+                    })
+                    .collect::<Result<Vec<(String, Option<Value>)>>>()?;
+                self.dedup(pairs, src)
+            }
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Not a Value::String. Can't split."),
+            ))),
+            None if self.split_none => Ok(Vec::new()),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
+    }
+
+    fn join_keyed(&self, pairs: &[(String, Option<Value>)]) -> Value {
+        let special = [self.pair_sep, self.kv_sep];
+        let quote = |s: &str| match &self.quoting {
+            Some(q) => q.quote(s, &special),
+            None => String::from(s),
+        };
+        Value::String(
+            pairs
+                .iter()
+                .map(|(k, v)| match v {
+                    Some(v) => format!("{}{}{}", quote(k), self.kv_sep, quote(&value_to_string(v))),
+                    None => quote(k),
+                })
+                .collect::<Vec<String>>()
+                .join(&self.pair_sep.to_string()),
+        )
+    }
+}
+
+/// Wraps a (string) splitter, so that it can split any `Value` variant. Non-string values are
+/// stringified (see `value_to_string`) before they are handed to the inner splitter.
+#[derive(Debug)]
+pub struct Stringifying<S>(pub S);
+
+impl<S> Stringifying<S> {
+    fn stringified(src: &Option<Value>) -> Cow<'_, Option<Value>> {
+        match src {
+            None | Some(Value::String(_)) => Cow::Borrowed(src),
+            Some(val) => Cow::Owned(Some(Value::String(value_to_string(val)))),
+        }
+    }
+}
+
+impl<S: Split> Split for Stringifying<S> {
+    fn split(&self, src: &Option<Value>) -> Result<(Option<Value>, Option<Value>)> {
+        self.0.split(&Self::stringified(src))
+    }
+
+    fn accepts_type(&self, _type_info: &Value) -> bool {
+        true
+    }
+}
+
+impl<S: SplitN> SplitN for Stringifying<S> {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        self.0.split_n(&Self::stringified(src))
+    }
+
+    fn accepts_type(&self, _type_info: &Value) -> bool {
+        true
+    }
+}
+
+impl<S: SplitNNamed> SplitNNamed for Stringifying<S> {
+    fn token_names(&self) -> Vec<Option<String>> {
+        self.0.token_names()
+    }
+}
+
+impl<S: SplitKeyed> SplitKeyed for Stringifying<S> {
+    fn split_keyed(&self, src: &Option<Value>) -> Result<Vec<(String, Option<Value>)>> {
+        self.0.split_keyed(&Self::stringified(src))
+    }
+
+    fn join_keyed(&self, pairs: &[(String, Option<Value>)]) -> Value {
+        self.0.join_keyed(pairs)
+    }
+
+    fn accepts_type(&self, _type_info: &Value) -> bool {
+        true
+    }
+}
+
+/// How empty strings (i.e. `Value::String("")`) are treated.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum EmptyPolicy {
+    /// Keep the empty string as is.
+    #[default]
+    Keep,
+    AsNone,
+    Fail,
+    /// Replace the empty string with the given value.
+    Default(Value),
+}
+
+/// Controls, how empty sources and empty tokens of a split are treated.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TokenPolicy {
+    /// Applied to the source value, before it is split.
+    pub empty_src: EmptyPolicy,
+    /// Applied to every (string) token, after `trim`.
+    pub empty_token: EmptyPolicy,
+    /// Trim whitespace around (string) tokens.
+    pub trim: bool,
+}
+
+/// Wraps any splitter and applies a `TokenPolicy` to its source value and resulting tokens.
+#[derive(Debug)]
+pub struct WithTokenPolicy<S> {
+    pub splitter: S,
+    pub policy: TokenPolicy,
+}
+
+impl<S> WithTokenPolicy<S> {
+    fn apply_to_src<'a>(&self, src: &'a Option<Value>) -> Result<Cow<'a, Option<Value>>> {
+        match (src, &self.policy.empty_src) {
+            (Some(Value::String(s)), policy) if s.is_empty() => match policy {
+                EmptyPolicy::Keep => Ok(Cow::Borrowed(src)),
+                EmptyPolicy::AsNone => Ok(Cow::Owned(None)),
+                EmptyPolicy::Fail => Err(VenumTdsTransRichError::Split(SplitError::from(
+                    String::from("Source Value is empty string, but empty_src policy is Fail"),
+                    src.clone(),
+                    None,
+                ))),
+                EmptyPolicy::Default(val) => Ok(Cow::Owned(Some(val.clone()))),
+            },
+            _ => Ok(Cow::Borrowed(src)),
+        }
+    }
+
+    fn apply_to_token(
+        &self,
+        src: &Option<Value>,
+        pos: usize,
+        token: Option<Value>,
+    ) -> Result<Option<Value>> {
+        let token = match token {
+            Some(Value::String(s)) if self.policy.trim && s.trim().len() != s.len() => {
+                Some(Value::String(String::from(s.trim())))
+            }
+            t => t,
+        };
+        match (&token, &self.policy.empty_token) {
+            (Some(Value::String(s)), policy) if s.is_empty() => match policy {
+                EmptyPolicy::Keep => Ok(token),
+                EmptyPolicy::AsNone => Ok(None),
+                EmptyPolicy::Fail => Err(VenumTdsTransRichError::Split(SplitError::from(
+                    String::from("Token is empty string, but empty_token policy is Fail"),
+                    src.clone(),
+                    Some(format!("token #{}", pos)),
+                ))),
+                EmptyPolicy::Default(val) => Ok(Some(val.clone())),
+            },
+            _ => Ok(token),
+        }
+    }
+}
+
+impl<S: Split> Split for WithTokenPolicy<S> {
+    fn split(&self, src: &Option<Value>) -> Result<(Option<Value>, Option<Value>)> {
+        let effective_src = self.apply_to_src(src)?;
+        let (left, right) = self.splitter.split(&effective_src)?;
+        Ok((
+            self.apply_to_token(src, 0, left)?,
+            self.apply_to_token(src, 1, right)?,
+        ))
+    }
+
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        self.splitter.accepts_type(type_info)
+    }
+}
+
+impl<S: SplitN> SplitN for WithTokenPolicy<S> {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        let effective_src = self.apply_to_src(src)?;
+        self.splitter
+            .split_n(&effective_src)?
+            .into_iter()
+            .enumerate()
+            .map(|(pos, token)| self.apply_to_token(src, pos, token))
+            .collect()
+    }
+
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        self.splitter.accepts_type(type_info)
+    }
+}
+
+impl<S: SplitNNamed> SplitNNamed for WithTokenPolicy<S> {
+    fn token_names(&self) -> Vec<Option<String>> {
+        self.splitter.token_names()
+    }
+}
+
+impl<S: SplitKeyed> SplitKeyed for WithTokenPolicy<S> {
+    fn split_keyed(&self, src: &Option<Value>) -> Result<Vec<(String, Option<Value>)>> {
+        let effective_src = self.apply_to_src(src)?;
+        self.splitter
+            .split_keyed(&effective_src)?
+            .into_iter()
+            .enumerate()
+            .map(|(pos, (key, token))| {
+                let key = match self.policy.trim {
+                    true => String::from(key.trim()),
+                    false => key,
+                };
+                Ok((key, self.apply_to_token(src, pos, token)?))
+            })
+            .collect()
+    }
+
+    fn join_keyed(&self, pairs: &[(String, Option<Value>)]) -> Value {
+        self.splitter.join_keyed(pairs)
+    }
+
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        self.splitter.accepts_type(type_info)
+    }
+}
+
+/// Splits a `Float32`, `Float64` or `Decimal` into its integer and fractional part (keeping the
+/// variant), i.e. `-1.25` into `(-1.0, -0.25)`. Note, that floats are subject to the usual rounding errors.
+#[derive(Debug)]
+pub struct ValueNumberFractionSplit {
+    pub split_none: bool,
+}
+
+impl Split for ValueNumberFractionSplit {
+    fn split(&self, src: &Option<Value>) -> Result<(Option<Value>, Option<Value>)> {
+        match src {
+            Some(Value::Float32(f)) => Ok((
+                Some(Value::Float32(f.trunc())),
+                Some(Value::Float32(f.fract())),
+            )),
+            Some(Value::Float64(f)) => Ok((
+                Some(Value::Float64(f.trunc())),
+                Some(Value::Float64(f.fract())),
+            )),
+            Some(Value::Decimal(d)) => Ok((
+                Some(Value::Decimal(d.trunc())),
+                Some(Value::Decimal(d.fract())),
+            )),
+            Some(_) => Err(VenumTdsTransRichError::Split(SplitError::from(
+                String::from("Not a Value::Float32|Float64|Decimal. Can't split."),
+                src.clone(),
+                None,
+            ))),
+            None if self.split_none => Ok((None, None)),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
+    }
+
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        matches!(
+            type_info,
+            Value::Float32(_) | Value::Float64(_) | Value::Decimal(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateTimeComponent {
+    /// `Value::NaiveDate`
+    Date,
+    /// `Value::Int32`
+    Year,
+    /// `Value::UInt32`, 1-4
+    Quarter,
+    /// `Value::UInt32`, 1-12
+    Month,
+    /// `Value::Int32`, the year the ISO week belongs to
+    IsoYear,
+    /// `Value::UInt32`, 1-53
+    IsoWeek,
+    /// `Value::UInt32`, 1 (Monday) - 7 (Sunday)
+    Weekday,
+    /// `Value::String`, "Mon" - "Sun"
+    WeekdayName,
+    /// `Value::UInt32`, 1-366
+    DayOfYear,
+    /// `Value::UInt32`, 1-31
+    Day,
+    /// `Value::UInt32`, 0-23
+    Hour,
+    /// `Value::UInt32`, 0-59
+    Minute,
+    /// `Value::UInt32`, 0-59 (60 for leap seconds)
+    Second,
+    /// `Value::Int64`, seconds since 1970-01-01T00:00:00Z. Naive values are taken as UTC.
+    EpochSeconds,
+    /// `Value::Int32`, the UTC offset in seconds. `None` for naive values.
+    OffsetSeconds,
+}
+
+impl DateTimeComponent {
+    pub fn name(&self) -> String {
+        String::from(match self {
+            DateTimeComponent::Date => "date",
+            DateTimeComponent::Year => "year",
+            DateTimeComponent::Quarter => "quarter",
+            DateTimeComponent::Month => "month",
+            DateTimeComponent::IsoYear => "iso_year",
+            DateTimeComponent::IsoWeek => "iso_week",
+            DateTimeComponent::Weekday => "weekday",
+            DateTimeComponent::WeekdayName => "weekday_name",
+            DateTimeComponent::DayOfYear => "day_of_year",
+            DateTimeComponent::Day => "day",
+            DateTimeComponent::Hour => "hour",
+            DateTimeComponent::Minute => "minute",
+            DateTimeComponent::Second => "second",
+            DateTimeComponent::EpochSeconds => "epoch_seconds",
+            DateTimeComponent::OffsetSeconds => "offset_seconds",
+        })
+    }
+}
+
+/// Splits a `NaiveDate`, `NaiveDateTime` or `DateTime` into the selected components, one token per
+/// component. `DateTime`s are split in their own offset. Time components of a `NaiveDate` are `None`.
+/// If `parse_fmt` is set, `Value::String`s are parsed with this (chrono) format first, trying
+/// `DateTime`, `NaiveDateTime` and `NaiveDate` (in that order).
+#[derive(Debug)]
+pub struct ValueDateTimeSplitN {
+    pub components: Vec<DateTimeComponent>,
+    pub parse_fmt: Option<String>,
+    pub split_none: bool,
+}
+
+struct DateTimeParts {
+    date: NaiveDate,
+    time: Option<NaiveTime>,
+    offset_secs: Option<i32>,
+    epoch_secs: i64,
+}
+
+impl ValueDateTimeSplitN {
+    fn parts(&self, src: &Option<Value>, val: &Value) -> Result<DateTimeParts> {
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .unwrap_or_default();
+        let from_naive = |dt: NaiveDateTime, time: Option<NaiveTime>| DateTimeParts {
+            date: dt.date(),
+            time,
+            offset_secs: None,
+            epoch_secs: (dt - epoch).num_seconds(),
+        };
+        let from_date_time = |dt: &DateTime<FixedOffset>| DateTimeParts {
+            date: dt.naive_local().date(),
+            time: Some(dt.naive_local().time()),
+            offset_secs: Some(dt.offset().local_minus_utc()),
+            epoch_secs: dt.timestamp(),
+        };
+
+        match (val, &self.parse_fmt) {
+            (Value::NaiveDate(d), _) => Ok(from_naive(d.and_time(NaiveTime::default()), None)),
+            (Value::NaiveDateTime(dt), _) => Ok(from_naive(*dt, Some(dt.time()))),
+            (Value::DateTime(dt), _) => Ok(from_date_time(dt)),
+            (Value::String(s), Some(fmt)) => {
+                if let Ok(dt) = DateTime::parse_from_str(s, fmt) {
+                    Ok(from_date_time(&dt))
+                } else if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
+                    Ok(from_naive(dt, Some(dt.time())))
+                } else {
+                    let d = NaiveDate::parse_from_str(s, fmt).map_err(|e| {
+                        VenumTdsTransRichError::Split(SplitError::from(
+                            String::from("Can't parse date/time. Can't split."),
+                            src.clone(),
+                            Some(format!("format: {}; {}", fmt, e)),
+                        ))
+                    })?;
+                    Ok(from_naive(d.and_time(NaiveTime::default()), None))
+                }
+            }
+            _ => Err(VenumTdsTransRichError::Split(SplitError::from(
+                String::from("Not a Value::NaiveDate|NaiveDateTime|DateTime. Can't split."),
+                src.clone(),
+                self.parse_fmt
+                    .as_ref()
+                    .map(|_| String::from("only Value::String can be parsed")),
+            ))),
+        }
+    }
+
+    fn component(parts: &DateTimeParts, component: &DateTimeComponent) -> Option<Value> {
+        let date = &parts.date;
+        match component {
+            DateTimeComponent::Date => Some(Value::NaiveDate(*date)),
+            DateTimeComponent::Year => Some(Value::Int32(date.year())),
+            DateTimeComponent::Quarter => Some(Value::UInt32((date.month() - 1) / 3 + 1)),
+            DateTimeComponent::Month => Some(Value::UInt32(date.month())),
+            DateTimeComponent::IsoYear => Some(Value::Int32(date.iso_week().year())),
+            DateTimeComponent::IsoWeek => Some(Value::UInt32(date.iso_week().week())),
+            DateTimeComponent::Weekday => Some(Value::UInt32(date.weekday().number_from_monday())),
+            DateTimeComponent::WeekdayName => Some(Value::String(date.weekday().to_string())),
+            DateTimeComponent::DayOfYear => Some(Value::UInt32(date.ordinal())),
+            DateTimeComponent::Day => Some(Value::UInt32(date.day())),
+            DateTimeComponent::Hour => parts.time.map(|t| Value::UInt32(t.hour())),
+            DateTimeComponent::Minute => parts.time.map(|t| Value::UInt32(t.minute())),
+            DateTimeComponent::Second => parts.time.map(|t| Value::UInt32(t.second())),
+            DateTimeComponent::EpochSeconds => Some(Value::Int64(parts.epoch_secs)),
+            DateTimeComponent::OffsetSeconds => parts.offset_secs.map(Value::Int32),
+        }
+    }
+}
+
+impl SplitN for ValueDateTimeSplitN {
+    fn split_n(&self, src: &Option<Value>) -> Result<Vec<Option<Value>>> {
+        match src {
+            Some(val) => {
+                let parts = self.parts(src, val)?;
+                Ok(self
+                    .components
+                    .iter()
+                    .map(|c| Self::component(&parts, c))
+                    .collect())
+            }
+            None if self.split_none => Ok(vec![None; self.components.len()]),
+            None => Err(VenumTdsTransRichError::Split(SplitError::minim(
+                String::from("Value is None, but split_none is false"),
+            ))),
+        }
+    }
+
+    fn accepts_type(&self, type_info: &Value) -> bool {
+        match type_info {
+            Value::NaiveDate(_) | Value::NaiveDateTime(_) | Value::DateTime(_) => true,
+            Value::String(_) => self.parse_fmt.is_some(),
+            _ => false,
+        }
+    }
+}
+
+impl SplitNNamed for ValueDateTimeSplitN {
+    fn token_names(&self) -> Vec<Option<String>> {
+        self.components.iter().map(|c| Some(c.name())).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_seperator_char() {
+        let sep = ValueStringSeparatorCharSplit {
+            sep_char: ' ',
+            split_none: true,
+        };
+        let data = Some(Value::from("foo bar".to_string()));
+        let split_res = sep.split(&data);
+        assert!(split_res.is_ok());
+        let split_vals = split_res.unwrap();
+        assert_eq!(Some(Value::from("foo".to_string())), split_vals.0);
+        assert_eq!(Some(Value::from("bar".to_string())), split_vals.1);
+    }
+
+    #[test]
+    fn test_split_seperator_char_none() {
+        let sep = ValueStringSeparatorCharSplit {
+            sep_char: ' ',
+            split_none: true,
+        };
+        let data = None;
+        let split_res = sep.split(&data);
+        assert!(split_res.is_ok());
+        let split_vals = split_res.unwrap();
+        assert_eq!(None, split_vals.0);
+        assert_eq!(None, split_vals.1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"expected 2 tokens as result of split, but got:"
+    )]
+    fn test_split_seperator_char_err() {
+        let sep = ValueStringSeparatorCharSplit {
+            sep_char: ' ',
+            split_none: true,
+        };
+        let data = Some(Value::from("foo bar baz".to_string()));
+        sep.split(&data).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"expected 2 tokens as result of split, but got: 1\", src_val: Some(String(\"foo\")), details: None })"
+    )]
+    fn test_split_seperator_char_err2() {
+        let sep = ValueStringSeparatorCharSplit {
+            sep_char: ' ',
+            split_none: true,
+        };
+        let data = Some(Value::from("foo".to_string()));
+        sep.split(&data).unwrap();
+    }
+
+    #[test]
+    fn test_split_n_seperator_char() {
+        let sep = ValueStringSeparatorCharSplitN {
+            sep_char: ' ',
+            split_none: false,
+            split_none_into_num_clones: None
+        };
+        let data = Some(Value::from("foo bar baz".to_string()));
+        let split_res = sep.split_n(&data);
+        assert!(split_res.is_ok());
+        let split_vals = split_res.unwrap();
+        assert_eq!(&Some(Value::from("foo".to_string())), split_vals.get(0).unwrap());
+        assert_eq!(&Some(Value::from("bar".to_string())), split_vals.get(1).unwrap());
+        assert_eq!(&Some(Value::from("baz".to_string())), split_vals.get(2).unwrap());
+    }
+
+    #[test]
+    fn test_split_n_seperator_char_none() {
+        let sep = ValueStringSeparatorCharSplitN {
+            sep_char: ' ',
+            split_none: true,
+            split_none_into_num_clones: Some(3),
+        };
+        let data = None;
+        let split_res = sep.split_n(&data);
+        assert!(split_res.is_ok());
+        let split_vals = split_res.unwrap();
+        assert_eq!(&None, split_vals.get(0).unwrap());
+        assert_eq!(&None, split_vals.get(1).unwrap());
+        assert_eq!(&None, split_vals.get(2).unwrap());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"Value is None, split_none is true, but split_none_into_num_clones is not set. Can't split into undefined number of targets!\", src_val: None, details: None })"
+    )]
+    fn test_split_n_seperator_char_none_err_config() {
+        let sep = ValueStringSeparatorCharSplitN {
+            sep_char: ' ',
+            split_none: true,
+            split_none_into_num_clones: None,
+        };
+        let data = None;
+        sep.split_n(&data).unwrap();
+    }
+
+    #[test]
+    fn test_split_n_regex_pair() {
+        let sep_res =
+            ValueStringRegexPairSplit::from("(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(), true);
+        assert!(sep_res.is_ok());
+        let sep = sep_res.unwrap();
+
+        let data = Some(Value::from("1.12 2.23".to_string()));
+        let split_res = sep.split(&data);
+        assert!(split_res.is_ok());
+        let split_vals = split_res.unwrap();
+        assert_eq!(Some(Value::from("1.12".to_string())), split_vals.0);
+        assert_eq!(Some(Value::from("2.23".to_string())), split_vals.1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"No captures, but we need exactly two.\""
+    )]
+    fn test_split_n_regex_err_no_captures() {
+        let sep_res =
+            ValueStringRegexPairSplit::from("(\\d+\\.\\d+).*(\\d+\\.\\d+).*(\\d+\\.\\d+)".to_string(), true);
+        assert!(sep_res.is_ok());
+        let sep = sep_res.unwrap();
+
+        let data = Some(Value::from("1.12 2.23".to_string()));
+        sep.split(&data).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"1 capture group(s), but we need exactly two.\""
+    )]
+    fn test_split_n_regex_err_too_few_capture_groups() {
+        let sep_res =
+            ValueStringRegexPairSplit::from("(\\d+\\.\\d+)".to_string(), true);
+        assert!(sep_res.is_ok());
+        let sep = sep_res.unwrap();
+
+        let data = Some(Value::from("1.12 2.23".to_string()));
+        sep.split(&data).unwrap();
+    }
+
+    #[test]
+    fn test_split_n_regex_pair_illegal_regex() {
+        let sep_res = ValueStringRegexPairSplit::from("FWPUJWDJW/)!(!()?))".to_string(), true);
+        assert!(sep_res.is_err());
+    }
+
+    #[test]
+    fn test_split_seperator_char_quoted() {
+        let sep = ValueStringSeparatorCharQuotedSplit {
+            sep_char: ';',
+            quoting: SeparatorQuoting::default(),
+            split_none: true,
+        };
+        let data = Some(Value::from("\"Smith; John\";42".to_string()));
+        let split_vals = sep.split(&data).unwrap();
+        assert_eq!(Some(Value::from("Smith; John".to_string())), split_vals.0);
+        assert_eq!(Some(Value::from("42".to_string())), split_vals.1);
+    }
+
+    #[test]
+    fn test_split_seperator_char_quoted_keep_quotes() {
+        let sep = ValueStringSeparatorCharQuotedSplit {
+            sep_char: ';',
+            quoting: SeparatorQuoting {
+                unquote: false,
+                ..SeparatorQuoting::default()
+            },
+            split_none: true,
+        };
+        let data = Some(Value::from("\"a;\"\"b\"\"\";c".to_string()));
+        let split_vals = sep.split(&data).unwrap();
+        assert_eq!(
+            Some(Value::from("\"a;\"\"b\"\"\"".to_string())),
+            split_vals.0
+        );
+        assert_eq!(Some(Value::from("c".to_string())), split_vals.1);
+    }
+
+    #[test]
+    fn test_split_n_seperator_char_quoted_escape_char() {
+        let sep = ValueStringSeparatorCharQuotedSplitN {
+            sep_char: ';',
+            quoting: SeparatorQuoting {
+                escape_char: Some('\\'),
+                ..SeparatorQuoting::default()
+            },
+            split_none: false,
+            split_none_into_num_clones: None,
+        };
+        let data = Some(Value::from(r#"a\;b;"say ""hi""";c"#.to_string()));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::from("a;b".to_string())),
+                Some(Value::from("say \"hi\"".to_string())),
+                Some(Value::from("c".to_string())),
+            ],
+            split_vals
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Split(SplitError { msg: \"unterminated quote (\\\")\"")]
+    fn test_split_n_seperator_char_quoted_err_unterminated_quote() {
+        let sep = ValueStringSeparatorCharQuotedSplitN {
+            sep_char: ';',
+            quoting: SeparatorQuoting::default(),
+            split_none: false,
+            split_none_into_num_clones: None,
+        };
+        let data = Some(Value::from("\"a;b".to_string()));
+        sep.split_n(&data).unwrap();
+    }
+
+    #[test]
+    fn test_split_n_regex() {
+        let sep = ValueStringRegexSplitN::from(String::from("(\\d+)-(\\d+)-(\\d+)"), true).unwrap();
+        let data = Some(Value::from("2022-10-17".to_string()));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::from("2022".to_string())),
+                Some(Value::from("10".to_string())),
+                Some(Value::from("17".to_string())),
+            ],
+            split_vals
+        );
+    }
+
+    #[test]
+    fn test_split_n_regex_optional_group_and_names() {
+        let sep = ValueStringRegexSplitN::from(
+            String::from("(?P<year>\\d{4})(-(?P<month>\\d{2}))?"),
+            true,
+        )
+        .unwrap();
+        let data = Some(Value::from("2022".to_string()));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![Some(Value::from("2022".to_string())), None, None],
+            split_vals
+        );
+        assert_eq!(
+            vec![
+                Some(String::from("year")),
+                None,
+                Some(String::from("month"))
+            ],
+            sep.token_names()
+        );
+    }
+
+    #[test]
+    fn test_split_n_regex_none() {
+        let sep = ValueStringRegexSplitN::from(String::from("(a)(b)"), true).unwrap();
+        assert_eq!(vec![None, None], sep.split_n(&None).unwrap());
+    }
+
+    #[test]
+    fn test_split_n_regex_err_no_groups() {
+        assert!(ValueStringRegexSplitN::from(String::from("\\d+"), true).is_err());
+    }
+
+    #[test]
+    fn test_split_separator_pair_last_occurrence() {
+        let sep = ValueStringSeparatorPairSplit {
+            separator: Separator::Char('.'),
+            position: SplitPosition::Last,
+            split_none: true,
+        };
+        let split_vals = sep
+            .split(&Some(Value::from("path/to/file.tar.gz".to_string())))
+            .unwrap();
+        assert_eq!(
+            Some(Value::from("path/to/file.tar".to_string())),
+            split_vals.0
+        );
+        assert_eq!(Some(Value::from("gz".to_string())), split_vals.1);
+    }
+
+    #[test]
+    fn test_split_separator_pair_first_and_nth_occurrence_str() {
+        let data = Some(Value::from("a::b::c".to_string()));
+        let sep = ValueStringSeparatorPairSplit {
+            separator: Separator::Str(String::from("::")),
+            position: SplitPosition::First,
+            split_none: true,
+        };
+        let split_vals = sep.split(&data).unwrap();
+        assert_eq!(Some(Value::from("a".to_string())), split_vals.0);
+        assert_eq!(Some(Value::from("b::c".to_string())), split_vals.1);
+
+        let sep = ValueStringSeparatorPairSplit {
+            separator: Separator::Str(String::from("::")),
+            position: SplitPosition::Nth(2),
+            split_none: true,
+        };
+        let split_vals = sep.split(&data).unwrap();
+        assert_eq!(Some(Value::from("a::b".to_string())), split_vals.0);
+        assert_eq!(Some(Value::from("c".to_string())), split_vals.1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"separator occurrence Nth(3) not found, got 2 occurrence(s)\""
+    )]
+    fn test_split_separator_pair_err_nth_occurrence_not_found() {
+        let sep = ValueStringSeparatorPairSplit {
+            separator: Separator::Char(';'),
+            position: SplitPosition::Nth(3),
+            split_none: true,
+        };
+        sep.split(&Some(Value::from("a;b;c".to_string()))).unwrap();
+    }
+
+    #[test]
+    fn test_split_separator_pair_regex() {
+        let sep = ValueStringSeparatorPairSplit {
+            separator: Separator::regex(String::from(r"\s*[,;]\s*")).unwrap(),
+            position: SplitPosition::First,
+            split_none: true,
+        };
+        let split_vals = sep
+            .split(&Some(Value::from("foo ;  bar, baz".to_string())))
+            .unwrap();
+        assert_eq!(Some(Value::from("foo".to_string())), split_vals.0);
+        assert_eq!(Some(Value::from("bar, baz".to_string())), split_vals.1);
+    }
+
+    #[test]
+    fn test_split_n_separator_max_splits() {
+        let data = Some(Value::from("a-b-c-d".to_string()));
+        let sep = ValueStringSeparatorSplitN {
+            separator: Separator::Char('-'),
+            max_splits: MaxSplits::FromLeft(2),
+            split_none: false,
+            split_none_into_num_clones: None,
+        };
+        assert_eq!(
+            vec![
+                Some(Value::from("a".to_string())),
+                Some(Value::from("b".to_string())),
+                Some(Value::from("c-d".to_string())),
+            ],
+            sep.split_n(&data).unwrap()
+        );
+
+        let sep = ValueStringSeparatorSplitN {
+            separator: Separator::Char('-'),
+            max_splits: MaxSplits::FromRight(1),
+            split_none: false,
+            split_none_into_num_clones: None,
+        };
+        assert_eq!(
+            vec![
+                Some(Value::from("a-b-c".to_string())),
+                Some(Value::from("d".to_string())),
+            ],
+            sep.split_n(&data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_split_n_separator_regex_unlimited() {
+        let sep = ValueStringSeparatorSplitN {
+            separator: Separator::regex(String::from(r"\s+")).unwrap(),
+            max_splits: MaxSplits::Unlimited,
+            split_none: true,
+            split_none_into_num_clones: Some(3),
+        };
+        assert_eq!(
+            vec![
+                Some(Value::from("foo".to_string())),
+                Some(Value::from("bar".to_string())),
+                Some(Value::from("baz".to_string())),
+            ],
+            sep.split_n(&Some(Value::from("foo  bar\tbaz".to_string())))
+                .unwrap()
+        );
+        assert_eq!(vec![None, None, None], sep.split_n(&None).unwrap());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"Separator is empty string. Can't split.\""
+    )]
+    fn test_split_n_separator_err_empty_str_separator() {
+        let sep = ValueStringSeparatorSplitN {
+            separator: Separator::Str(String::new()),
+            max_splits: MaxSplits::Unlimited,
+            split_none: false,
+            split_none_into_num_clones: None,
+        };
+        sep.split_n(&Some(Value::from("foo".to_string()))).unwrap();
+    }
+
+    #[test]
+    fn test_split_n_fixed_width() {
+        let sep = ValueStringFixedWidthSplitN {
+            ranges: ValueStringFixedWidthSplitN::ranges_from_widths(&[5, 4, 3]),
+            unit: FixedWidthUnit::Chars,
+            trim: true,
+            blank_as_none: true,
+            short_record: FixedWidthShortRecord::Fail,
+            split_none: false,
+        };
+        let data = Some(Value::from("Jörg   42   ".to_string()));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::from("Jörg".to_string())),
+                Some(Value::from("42".to_string())),
+                None,
+            ],
+            split_vals
+        );
+    }
+
+    #[test]
+    fn test_split_n_fixed_width_short_record_pad() {
+        let sep = ValueStringFixedWidthSplitN {
+            ranges: vec![0..2, 2..6, 6..8],
+            unit: FixedWidthUnit::Bytes,
+            trim: false,
+            blank_as_none: false,
+            short_record: FixedWidthShortRecord::Pad,
+            split_none: false,
+        };
+        let data = Some(Value::from("abcd".to_string()));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::from("ab".to_string())),
+                Some(Value::from("cd".to_string())),
+                None,
+            ],
+            split_vals
+        );
+    }
+
+    #[test]
+    fn test_split_n_fixed_width_short_record_pad_zero_width() {
+        let sep = ValueStringFixedWidthSplitN {
+            ranges: ValueStringFixedWidthSplitN::ranges_from_widths(&[2, 2, 3, 0]),
+            unit: FixedWidthUnit::Chars,
+            trim: false,
+            blank_as_none: false,
+            short_record: FixedWidthShortRecord::Pad,
+            split_none: false,
+        };
+        let data = Some(Value::from("abc".to_string()));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::from("ab".to_string())),
+                Some(Value::from("c".to_string())),
+                None,
+                None,
+            ],
+            split_vals
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"record too short. Field 2..6 exceeds record length: 4\""
+    )]
+    fn test_split_n_fixed_width_short_record_err() {
+        let sep = ValueStringFixedWidthSplitN {
+            ranges: vec![0..2, 2..6],
+            unit: FixedWidthUnit::Chars,
+            trim: false,
+            blank_as_none: false,
+            short_record: FixedWidthShortRecord::Fail,
+            split_none: false,
+        };
+        let data = Some(Value::from("abcd".to_string()));
+        sep.split_n(&data).unwrap();
+    }
+
+    #[test]
+    fn test_split_keyed_key_value() {
+        let sep = ValueStringKeyValueSplit {
+            pair_sep: ';',
+            kv_sep: '=',
+            quoting: None,
+            duplicate_keys: DuplicateKeys::Fail,
+            split_none: false,
+        };
+        let data = Some(Value::from("color=red;size=XL;;flag;url=a=b".to_string()));
+        let split_vals = sep.split_keyed(&data).unwrap();
+        assert_eq!(
+            vec![
+                (String::from("color"), Some(Value::from("red".to_string()))),
+                (String::from("size"), Some(Value::from("XL".to_string()))),
+                (String::from("flag"), None),
+                (String::from("url"), Some(Value::from("a=b".to_string()))),
+            ],
+            split_vals
+        );
+    }
+
+    #[test]
+    fn test_split_keyed_key_value_duplicate_keys() {
+        let split = |duplicate_keys| {
+            ValueStringKeyValueSplit {
+                pair_sep: ';',
+                kv_sep: '=',
+                quoting: None,
+                duplicate_keys,
+                split_none: false,
+            }
+            .split_keyed(&Some(Value::from("a=1;b=2;a=3".to_string())))
+        };
+        let pairs = |a: &str| {
+            vec![
+                (String::from("a"), Some(Value::from(a.to_string()))),
+                (String::from("b"), Some(Value::from("2".to_string()))),
+            ]
+        };
+        assert_eq!(pairs("1"), split(DuplicateKeys::First).unwrap());
+        assert_eq!(pairs("3"), split(DuplicateKeys::Last).unwrap());
+        assert_eq!(
+            Err(VenumTdsTransRichError::Split(SplitError::from(
+                String::from("duplicate key: a"),
+                Some(Value::from("a=1;b=2;a=3".to_string())),
+                None,
+            ))),
+            split(DuplicateKeys::Fail)
+        );
+    }
+
+    #[test]
+    fn test_split_keyed_key_value_quoted() {
+        let sep = ValueStringKeyValueSplit {
+            pair_sep: ';',
+            kv_sep: '=',
+            quoting: Some(SeparatorQuoting::default()),
+            duplicate_keys: DuplicateKeys::Fail,
+            split_none: false,
+        };
+        let data = Some(Value::from("name=\"Smith; John\";\"a=b\"=1".to_string()));
+        let split_vals = sep.split_keyed(&data).unwrap();
+        assert_eq!(
+            vec![
+                (
+                    String::from("name"),
+                    Some(Value::from("Smith; John".to_string()))
+                ),
+                (String::from("a=b"), Some(Value::from("1".to_string()))),
+            ],
+            split_vals
+        );
+        assert_eq!(
+            Value::from("name=\"Smith; John\";\"a=b\"=1".to_string()),
+            sep.join_keyed(&split_vals)
+        );
+    }
+
+    #[test]
+    fn test_split_n_json() {
+        let sep = ValueStringJsonSplitN::from(
+            vec![
+                String::from("id"),
+                String::from("/price"),
+                String::from("tags.1"),
+                String::from("active"),
+                String::from("dims"),
+                String::from("note"),
+                String::from("missing.path"),
+            ],
+            true,
+            false,
+        )
+        .unwrap();
+        let data = Some(Value::from(
+            r#"{"id": 42, "price": 1.5, "tags": ["a", "b"], "active": true, "dims": {"w": 1}, "note": null}"#
+                .to_string(),
+        ));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::Int64(42)),
+                Some(Value::Float64(1.5)),
+                Some(Value::from("b".to_string())),
+                Some(Value::Bool(true)),
+                Some(Value::from(r#"{"w":1}"#.to_string())),
+                None,
+                None,
+            ],
+            split_vals
+        );
+        assert_eq!("tags.1", sep.paths()[2]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"path not found in JSON document\", src_val: Some(String(\"{}\")), details: Some(\"path: a.b\") })"
+    )]
+    fn test_split_n_json_err_missing_path() {
+        let sep = ValueStringJsonSplitN::from(vec![String::from("a.b")], false, false).unwrap();
+        sep.split_n(&Some(Value::from("{}".to_string()))).unwrap();
+    }
+
+    #[test]
+    fn test_split_n_json_err_illegal_path_and_document() {
+        assert!(ValueStringJsonSplitN::from(vec![String::from("a..b")], true, false).is_err());
+        let sep = ValueStringJsonSplitN::from(vec![String::from("a")], true, false).unwrap();
+        assert!(sep.split_n(&Some(Value::from("{a:".to_string()))).is_err());
+    }
+
+    #[test]
+    fn test_split_n_url() {
+        let sep = ValueStringUrlSplitN {
+            components: vec![
+                UrlComponent::Scheme,
+                UrlComponent::User,
+                UrlComponent::Host,
+                UrlComponent::Port,
+                UrlComponent::Path,
+                UrlComponent::Query,
+                UrlComponent::Fragment,
+                UrlComponent::QueryParam(String::from("utm_source")),
+                UrlComponent::QueryParam(String::from("missing")),
+            ],
+            split_none: false,
+        };
+        let data = Some(Value::from(
+            "https://jane@example.com/a/b?utm_source=news%20letter&x=1#top".to_string(),
+        ));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::from("https".to_string())),
+                Some(Value::from("jane".to_string())),
+                Some(Value::from("example.com".to_string())),
+                Some(Value::UInt16(443)),
+                Some(Value::from("/a/b".to_string())),
+                Some(Value::from("utm_source=news%20letter&x=1".to_string())),
+                Some(Value::from("top".to_string())),
+                Some(Value::from("news letter".to_string())),
+                None,
+            ],
+            split_vals
+        );
+        assert_eq!(Some(String::from("query.utm_source")), sep.token_names()[7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Split(SplitError { msg: \"Not a valid URL. Can't split.\"")]
+    fn test_split_n_url_err_relative_url() {
+        let sep = ValueStringUrlSplitN {
+            components: vec![UrlComponent::Host],
+            split_none: false,
+        };
+        sep.split_n(&Some(Value::from("/just/a/path".to_string())))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_split_stringifying() {
+        let sep = Stringifying(ValueStringSeparatorCharSplit {
+            sep_char: '.',
+            split_none: true,
+        });
+        let data = Some(Value::Float64(12.5));
+        let split_vals = sep.split(&data).unwrap();
+        assert_eq!(Some(Value::from("12".to_string())), split_vals.0);
+        assert_eq!(Some(Value::from("5".to_string())), split_vals.1);
+    }
+
+    #[test]
+    fn test_split_number_fraction() {
+        let sep = ValueNumberFractionSplit { split_none: true };
+        let split_vals = sep.split(&Some(Value::Float64(-1.25))).unwrap();
+        assert_eq!(Some(Value::Float64(-1.0)), split_vals.0);
+        assert_eq!(Some(Value::Float64(-0.25)), split_vals.1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"Not a Value::Float32|Float64|Decimal. Can't split.\""
+    )]
+    fn test_split_number_fraction_err_not_a_number() {
+        let sep = ValueNumberFractionSplit { split_none: true };
+        sep.split(&Some(Value::from("1.25".to_string()))).unwrap();
+    }
+
+    #[test]
+    fn test_split_n_date_time() {
+        let sep = ValueDateTimeSplitN {
+            components: vec![
+                DateTimeComponent::Date,
+                DateTimeComponent::Year,
+                DateTimeComponent::Month,
+                DateTimeComponent::Day,
+                DateTimeComponent::Hour,
+                DateTimeComponent::Second,
+            ],
+            parse_fmt: None,
+            split_none: false,
+        };
+        let date = NaiveDate::from_ymd_opt(2022, 10, 17).unwrap();
+        let data = Some(Value::NaiveDateTime(date.and_hms_opt(13, 14, 15).unwrap()));
+        let split_vals = sep.split_n(&data).unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::NaiveDate(date)),
+                Some(Value::Int32(2022)),
+                Some(Value::UInt32(10)),
+                Some(Value::UInt32(17)),
+                Some(Value::UInt32(13)),
+                Some(Value::UInt32(15)),
+            ],
+            split_vals
+        );
+
+        let split_vals = sep.split_n(&Some(Value::NaiveDate(date))).unwrap();
+        assert_eq!(None, split_vals[4]);
+    }
+
+    #[test]
+    fn test_split_n_date_time_parsed_from_string() {
+        let sep = ValueDateTimeSplitN {
+            components: vec![
+                DateTimeComponent::Quarter,
+                DateTimeComponent::IsoYear,
+                DateTimeComponent::IsoWeek,
+                DateTimeComponent::Weekday,
+                DateTimeComponent::WeekdayName,
+                DateTimeComponent::DayOfYear,
+                DateTimeComponent::Hour,
+                DateTimeComponent::EpochSeconds,
+                DateTimeComponent::OffsetSeconds,
+            ],
+            parse_fmt: Some(String::from("%d.%m.%Y")),
+            split_none: false,
+        };
+        let split_vals = sep
+            .split_n(&Some(Value::from("01.01.2021".to_string())))
+            .unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::UInt32(1)),
+                Some(Value::Int32(2020)),
+                Some(Value::UInt32(53)),
+                Some(Value::UInt32(5)),
+                Some(Value::from("Fri".to_string())),
+                Some(Value::UInt32(1)),
+                None,
+                Some(Value::Int64(1609459200)),
+                None,
+            ],
+            split_vals
+        );
+    }
+
+    #[test]
+    fn test_split_n_date_time_with_offset() {
+        let sep = ValueDateTimeSplitN {
+            components: vec![
+                DateTimeComponent::Hour,
+                DateTimeComponent::EpochSeconds,
+                DateTimeComponent::OffsetSeconds,
+            ],
+            parse_fmt: Some(String::from("%Y-%m-%dT%H:%M:%S%z")),
+            split_none: false,
+        };
+        let split_vals = sep
+            .split_n(&Some(Value::from("2021-01-01T02:00:00+0200".to_string())))
+            .unwrap();
+        assert_eq!(
+            vec![
+                Some(Value::UInt32(2)),
+                Some(Value::Int64(1609459200)),
+                Some(Value::Int32(7200)),
+            ],
+            split_vals
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Split(SplitError { msg: \"Can't parse date/time. Can't split.\"")]
+    fn test_split_n_date_time_err_parse() {
+        let sep = ValueDateTimeSplitN {
+            components: vec![DateTimeComponent::Year],
+            parse_fmt: Some(String::from("%Y-%m-%d")),
+            split_none: false,
+        };
+        sep.split_n(&Some(Value::from("17.10.2022".to_string())))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_split_with_token_policy_empty_token_as_none_and_trim() {
+        let sep = WithTokenPolicy {
+            splitter: ValueStringSeparatorCharSplit {
+                sep_char: ';',
+                split_none: true,
+            },
+            policy: TokenPolicy {
+                empty_token: EmptyPolicy::AsNone,
+                trim: true,
+                ..TokenPolicy::default()
+            },
+        };
+        let split_vals = sep.split(&Some(Value::from(" a ;  ".to_string()))).unwrap();
+        assert_eq!(Some(Value::from("a".to_string())), split_vals.0);
+        assert_eq!(None, split_vals.1);
+    }
+
+    #[test]
+    fn test_split_n_with_token_policy_empty_src_as_none_and_default_token() {
+        let sep = WithTokenPolicy {
+            splitter: ValueStringSeparatorCharSplitN {
+                sep_char: ';',
+                split_none: true,
+                split_none_into_num_clones: Some(2),
+            },
+            policy: TokenPolicy {
+                empty_src: EmptyPolicy::AsNone,
+                empty_token: EmptyPolicy::Default(Value::from("0".to_string())),
+                trim: false,
+            },
+        };
+        assert_eq!(
+            vec![None, None],
+            sep.split_n(&Some(Value::from(String::new()))).unwrap()
+        );
+        assert_eq!(
+            vec![
+                Some(Value::from("1".to_string())),
+                Some(Value::from("0".to_string()))
+            ],
+            sep.split_n(&Some(Value::from("1;".to_string()))).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Split(SplitError { msg: \"Token is empty string, but empty_token policy is Fail\", src_val: Some(String(\";b\")), details: Some(\"token #0\") })"
+    )]
+    fn test_split_with_token_policy_err_empty_token() {
+        let sep = WithTokenPolicy {
+            splitter: ValueStringSeparatorCharSplit {
+                sep_char: ';',
+                split_none: true,
+            },
+            policy: TokenPolicy {
+                empty_token: EmptyPolicy::Fail,
+                ..TokenPolicy::default()
+            },
+        };
+        sep.split(&Some(Value::from(";b".to_string()))).unwrap();
     }
 }